@@ -0,0 +1,126 @@
+mod server;
+
+pub use server::*;
+
+use {
+    crate::types::common::{ArbitrageError, ArbitrageOpportunity},
+    serde::{Deserialize, Serialize},
+    solana_sdk::pubkey::Pubkey,
+    tokio::sync::{mpsc, oneshot},
+};
+
+// Admin requests wait behind at most this many others before being rejected
+const COMMAND_QUEUE: usize = 32;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyStatus {
+    pub name: String,
+    // `engine` for the scanner's own routes, `factory` for `StrategyFactory` strategies, which are listed as
+    // `factory:<name>` and cannot be paused
+    pub source: &'static str,
+    pub description: String,
+    pub paused: bool,
+    pub found: u64,
+    pub found_last_cycle: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineStatus {
+    pub stopped: bool,
+    pub in_flight: usize,
+    pub strategies: Vec<StrategyStatus>,
+    pub paused_markets: Vec<String>,
+    pub min_profit_threshold: f64,
+    pub max_position_size: u64,
+    pub max_concurrent_trades: u32,
+    pub slippage_tolerance: f64,
+}
+
+// Unset fields are left as they are
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdUpdate {
+    pub min_profit_threshold: Option<f64>,
+    pub max_position_size: Option<u64>,
+    pub max_concurrent_trades: Option<u32>,
+    pub slippage_tolerance: Option<f64>,
+}
+
+// Requests the engine applies between opportunities; it owns all the state they touch
+pub enum AdminCommand {
+    Status(oneshot::Sender<EngineStatus>),
+    PauseStrategy {
+        strategy: String,
+        paused: bool,
+        reply: oneshot::Sender<Result<(), ArbitrageError>>,
+    },
+    PauseMarket {
+        market: Pubkey,
+        paused: bool,
+        reply: oneshot::Sender<()>,
+    },
+    UpdateThresholds {
+        update: ThresholdUpdate,
+        reply: oneshot::Sender<Result<Vec<String>, ArbitrageError>>,
+    },
+    // Stop taking opportunities and drop trades that have not been sent yet
+    EmergencyStop(oneshot::Sender<()>),
+    Resume(oneshot::Sender<()>),
+    TopOpportunities {
+        limit: usize,
+        reply: oneshot::Sender<Vec<ArbitrageOpportunity>>,
+    },
+}
+
+pub type AdminReceiver = mpsc::Receiver<AdminCommand>;
+
+#[derive(Clone)]
+pub struct AdminHandle {
+    sender: mpsc::Sender<AdminCommand>,
+}
+
+pub fn admin_channel() -> (AdminHandle, AdminReceiver) {
+    let (sender, receiver) = mpsc::channel(COMMAND_QUEUE);
+    (AdminHandle { sender }, receiver)
+}
+
+impl AdminHandle {
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> AdminCommand) -> Result<T, ArbitrageError> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .try_send(command(reply))
+            .map_err(|e| ArbitrageError::ConfigError(format!("Engine is not taking admin commands: {}", e)))?;
+        response
+            .await
+            .map_err(|_| ArbitrageError::ConfigError("Engine dropped the admin command".to_string()))
+    }
+
+    pub async fn status(&self) -> Result<EngineStatus, ArbitrageError> {
+        self.request(AdminCommand::Status).await
+    }
+
+    pub async fn pause_strategy(&self, strategy: &str, paused: bool) -> Result<(), ArbitrageError> {
+        let strategy = strategy.to_string();
+        self.request(|reply| AdminCommand::PauseStrategy { strategy, paused, reply }).await?
+    }
+
+    pub async fn pause_market(&self, market: Pubkey, paused: bool) -> Result<(), ArbitrageError> {
+        self.request(|reply| AdminCommand::PauseMarket { market, paused, reply }).await
+    }
+
+    pub async fn update_thresholds(&self, update: ThresholdUpdate) -> Result<Vec<String>, ArbitrageError> {
+        self.request(|reply| AdminCommand::UpdateThresholds { update, reply }).await?
+    }
+
+    pub async fn emergency_stop(&self) -> Result<(), ArbitrageError> {
+        self.request(AdminCommand::EmergencyStop).await
+    }
+
+    pub async fn resume(&self) -> Result<(), ArbitrageError> {
+        self.request(AdminCommand::Resume).await
+    }
+
+    pub async fn top_opportunities(&self, limit: usize) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
+        self.request(|reply| AdminCommand::TopOpportunities { limit, reply }).await
+    }
+}
@@ -0,0 +1,289 @@
+use {
+    super::{AdminHandle, ThresholdUpdate},
+    crate::{config::AdminSettings, inventory::InventoryTracker, types::common::ArbitrageError},
+    axum::{
+        extract::{Path, Query, State},
+        http::{header, Request, StatusCode},
+        middleware::{self, Next},
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    },
+    serde::Deserialize,
+    serde_json::json,
+    sha2::{Digest, Sha256},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        net::{SocketAddr, TcpListener},
+        str::FromStr,
+        sync::Arc,
+    },
+};
+
+const MIN_TOKEN_LEN: usize = 32;
+const DEFAULT_OPPORTUNITIES: usize = 20;
+
+#[derive(Clone)]
+struct AdminState {
+    engine: AdminHandle,
+    inventory: Arc<InventoryTracker>,
+}
+
+#[derive(Deserialize)]
+struct EmergencyStopRequest {
+    #[serde(default)]
+    flatten: bool,
+}
+
+#[derive(Deserialize)]
+struct OpportunitiesQuery {
+    limit: Option<usize>,
+}
+
+struct ApiError(ArbitrageError);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            ArbitrageError::ConfigError(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(json!({ "error": self.0.to_string() }))).into_response()
+    }
+}
+
+impl From<ArbitrageError> for ApiError {
+    fn from(e: ArbitrageError) -> Self {
+        Self(e)
+    }
+}
+
+type ApiResult = Result<Response, ApiError>;
+
+pub async fn serve_admin(
+    settings: &AdminSettings,
+    engine: AdminHandle,
+    inventory: Arc<InventoryTracker>,
+) -> Result<(), ArbitrageError> {
+    let token = std::env::var(&settings.token_env)
+        .map_err(|_| ArbitrageError::ConfigError(format!("Admin API token {} is not set", settings.token_env)))?;
+    if token.len() < MIN_TOKEN_LEN {
+        return Err(ArbitrageError::ConfigError(format!(
+            "Admin API token {} must be at least {} characters",
+            settings.token_env, MIN_TOKEN_LEN
+        )));
+    }
+    let address: SocketAddr = settings
+        .address
+        .parse()
+        .map_err(|e| ArbitrageError::ConfigError(format!("Invalid admin address {}: {}", settings.address, e)))?;
+
+    let listener = TcpListener::bind(address)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to bind admin API on {}: {}", address, e)))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| ArbitrageError::NetworkError(format!("Admin API failed: {}", e)))?;
+    log::info!("Serving admin API on http://{}", address);
+
+    axum::Server::from_tcp(listener)
+        .map_err(|e| ArbitrageError::NetworkError(format!("Admin API failed: {}", e)))?
+        .serve(admin_routes(engine, inventory, &token).into_make_service())
+        .await
+        .map_err(|e| ArbitrageError::NetworkError(format!("Admin API failed: {}", e)))
+}
+
+fn admin_routes(engine: AdminHandle, inventory: Arc<InventoryTracker>, token: &str) -> Router {
+    let token_digest = Arc::new(Sha256::digest(token.as_bytes()).to_vec());
+
+    Router::new()
+        .route("/strategies", get(status))
+        .route("/strategies/:strategy/pause", post(pause_strategy))
+        .route("/strategies/:strategy/resume", post(resume_strategy))
+        .route("/markets/:market/pause", post(pause_market))
+        .route("/markets/:market/resume", post(resume_market))
+        .route("/thresholds", post(update_thresholds))
+        .route("/opportunities", get(top_opportunities))
+        .route("/emergency-stop", post(emergency_stop))
+        .route("/resume", post(resume))
+        .with_state(AdminState { engine, inventory })
+        .layer(middleware::from_fn_with_state(token_digest, authorize))
+}
+
+// Digests are compared so the time taken says nothing about the token itself
+async fn authorize<B>(State(token_digest): State<Arc<Vec<u8>>>, request: Request<B>, next: Next<B>) -> Response {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match presented {
+        Some(token) if Sha256::digest(token.as_bytes()).as_slice() == token_digest.as_slice() => {
+            next.run(request).await
+        }
+        _ => (StatusCode::UNAUTHORIZED, Json(json!({ "error": "missing or invalid bearer token" }))).into_response(),
+    }
+}
+
+async fn status(State(state): State<AdminState>) -> ApiResult {
+    Ok(Json(state.engine.status().await?).into_response())
+}
+
+async fn pause_strategy(State(state): State<AdminState>, Path(strategy): Path<String>) -> ApiResult {
+    state.engine.pause_strategy(&strategy, true).await?;
+    log::warn!("Admin paused strategy {}", strategy);
+    Ok(Json(json!({ "strategy": strategy, "paused": true })).into_response())
+}
+
+async fn resume_strategy(State(state): State<AdminState>, Path(strategy): Path<String>) -> ApiResult {
+    state.engine.pause_strategy(&strategy, false).await?;
+    log::warn!("Admin resumed strategy {}", strategy);
+    Ok(Json(json!({ "strategy": strategy, "paused": false })).into_response())
+}
+
+fn parse_market(market: &str) -> Result<Pubkey, ApiError> {
+    Pubkey::from_str(market)
+        .map_err(|e| ApiError(ArbitrageError::ConfigError(format!("Invalid market {}: {}", market, e))))
+}
+
+async fn pause_market(State(state): State<AdminState>, Path(market): Path<String>) -> ApiResult {
+    state.engine.pause_market(parse_market(&market)?, true).await?;
+    log::warn!("Admin paused market {}", market);
+    Ok(Json(json!({ "market": market, "paused": true })).into_response())
+}
+
+async fn resume_market(State(state): State<AdminState>, Path(market): Path<String>) -> ApiResult {
+    state.engine.pause_market(parse_market(&market)?, false).await?;
+    log::warn!("Admin resumed market {}", market);
+    Ok(Json(json!({ "market": market, "paused": false })).into_response())
+}
+
+async fn update_thresholds(State(state): State<AdminState>, Json(update): Json<ThresholdUpdate>) -> ApiResult {
+    let changes = state.engine.update_thresholds(update).await?;
+    log::warn!("Admin updated thresholds: {}", changes.join(", "));
+    Ok(Json(json!({ "changes": changes })).into_response())
+}
+
+async fn top_opportunities(State(state): State<AdminState>, Query(query): Query<OpportunitiesQuery>) -> ApiResult {
+    let opportunities = state
+        .engine
+        .top_opportunities(query.limit.unwrap_or(DEFAULT_OPPORTUNITIES))
+        .await?;
+    Ok(Json(opportunities).into_response())
+}
+
+// `flatten` is a query parameter so a malformed request is refused with 400 instead of stopping without flattening
+async fn emergency_stop(State(state): State<AdminState>, Query(request): Query<EmergencyStopRequest>) -> ApiResult {
+    state.engine.emergency_stop().await?;
+    log::error!("Admin triggered an emergency stop");
+
+    let flattened = if request.flatten { Some(state.inventory.flatten().await?) } else { None };
+    Ok(Json(json!({ "stopped": true, "flattened": flattened })).into_response())
+}
+
+async fn resume(State(state): State<AdminState>) -> ApiResult {
+    state.engine.resume().await?;
+    log::warn!("Admin resumed trading after an emergency stop");
+    Ok(Json(json!({ "stopped": false })).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            admin::{admin_channel, AdminCommand, EngineStatus},
+            config::{Settings, SettingsHandle},
+            dex::DexRegistry,
+            journal::Journal,
+            monitoring::alert_channel,
+            wallet::ExecutionPool,
+        },
+        solana_client::rpc_client::RpcClient,
+        std::sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+    #[tokio::test]
+    async fn test_admin_api_requires_token_and_reaches_engine() {
+        let settings = SettingsHandle::new(Settings::default());
+        let path = std::env::temp_dir().join(format!("arb-admin-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let journal = Arc::new(Journal::open(&path).unwrap());
+        let rpc_client = Arc::new(RpcClient::new("http://127.0.0.1:1".to_string()));
+        let pool = Arc::new(ExecutionPool::new(settings.clone(), rpc_client, journal, vec![]));
+        let (alerts, _alert_receiver) = alert_channel();
+        let inventory = Arc::new(InventoryTracker::new(settings, pool, Arc::new(DexRegistry::new()), alerts));
+
+        // Stand-in for the engine loop
+        let (handle, mut commands) = admin_channel();
+        let stops = Arc::new(AtomicUsize::new(0));
+        let engine_stops = stops.clone();
+        tokio::spawn(async move {
+            while let Some(command) = commands.recv().await {
+                match command {
+                    AdminCommand::PauseStrategy { strategy, reply, .. } => {
+                        let result = match strategy.as_str() {
+                            "jit" => Ok(()),
+                            _ => Err(ArbitrageError::ConfigError(format!("Unknown strategy {}", strategy))),
+                        };
+                        let _ = reply.send(result);
+                    }
+                    AdminCommand::EmergencyStop(reply) => {
+                        engine_stops.fetch_add(1, Ordering::SeqCst);
+                        let _ = reply.send(());
+                    }
+                    AdminCommand::Status(reply) => {
+                        let _ = reply.send(EngineStatus {
+                            stopped: true,
+                            in_flight: 0,
+                            strategies: vec![],
+                            paused_markets: vec![],
+                            min_profit_threshold: 0.01,
+                            max_position_size: 0,
+                            max_concurrent_trades: 1,
+                            slippage_tolerance: 0.01,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = admin_routes(handle, inventory, TOKEN);
+        tokio::spawn(axum::Server::from_tcp(listener).unwrap().serve(app.into_make_service()));
+
+        let client = reqwest::Client::new();
+        let url = |path: &str| format!("http://{}{}", address, path);
+
+        let response = client.get(url("/strategies")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = client.get(url("/strategies")).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = client.post(url("/strategies/jit/pause")).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = client.post(url("/strategies/nope/pause")).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = client.post(url("/emergency-stop?flatten=maybe")).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(stops.load(Ordering::SeqCst), 0);
+
+        let response = client.post(url("/emergency-stop")).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(stops.load(Ordering::SeqCst), 1);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body, json!({ "stopped": true, "flattened": null }));
+
+        let status: serde_json::Value =
+            client.get(url("/strategies")).bearer_auth(TOKEN).send().await.unwrap().json().await.unwrap();
+        assert_eq!(status["stopped"], true);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,287 @@
+use {
+    clap::{Parser, Subcommand, ValueEnum},
+    std::path::PathBuf,
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "solana-arbitrage-bot", version, about)]
+pub struct Cli {
+    /// Path to a TOML settings file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Point the RPC and websocket endpoints at a public cluster, dropping `network.backup_nodes`; `--set` still
+    /// takes precedence
+    #[arg(long, global = true, value_enum)]
+    pub network: Option<NetworkProfile>,
+
+    /// Format of the log lines written to stderr
+    #[arg(long, global = true, value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// Override a setting by its dotted key, e.g. `trading.execution.max_concurrent_trades=5`
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+
+    /// Runs the bot when omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    // Global flags expressed as `--set` overrides, ahead of the explicit ones so those win
+    pub fn settings_overrides(&self) -> Vec<String> {
+        let mut overrides = Vec::new();
+        if let Some(network) = self.network {
+            let (rpc, ws) = network.endpoints();
+            overrides.push(format!("network.rpc_endpoints={}", rpc));
+            overrides.push(format!("network.ws_endpoints={}", ws));
+            // Backups configured for another cluster would serve the wrong chain
+            overrides.push("network.backup_nodes=".to_string());
+        }
+        if let Some(format) = self.log_format {
+            overrides.push(format!("monitoring.log_format={}", format.as_str()));
+        }
+        overrides.extend(self.overrides.iter().cloned());
+        overrides
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Trade: scan markets and execute profitable opportunities
+    Run,
+
+    /// Print the opportunities the engine finds without trading
+    Scan {
+        /// Stop after a single pass instead of scanning every `--interval-ms`
+        #[arg(long)]
+        once: bool,
+
+        #[arg(long, default_value_t = 1000)]
+        interval_ms: u64,
+
+        /// Print each opportunity as one JSON object per line
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Quote a swap of `amount` against one pool's current state
+    Quote {
+        /// Market (pool) address
+        pool: String,
+
+        /// Amount in the token's base units; sold for the quote token and spent buying the base token
+        amount: u64,
+    },
+
+    /// Inspect whitelisted markets
+    #[command(subcommand)]
+    Markets(MarketsCommand),
+
+    /// Inspect the execution wallets
+    #[command(subcommand)]
+    Wallet(WalletCommand),
+
+    /// Validate the settings
+    #[command(subcommand)]
+    Config(ConfigCommand),
+
+    /// Re-quote and simulate a saved opportunity without sending it
+    Simulate {
+        /// JSON file holding one opportunity, as printed by `scan --json`
+        #[arg(value_name = "OPPORTUNITY_JSON")]
+        path: PathBuf,
+    },
+
+    /// Manage the encrypted keystore
+    #[command(subcommand)]
+    Keystore(KeystoreCommand),
+
+    /// Run or manage the standalone signing service
+    #[command(subcommand)]
+    Signer(SignerCommand),
+
+    /// Inspect or reset the circuit breaker
+    #[command(subcommand)]
+    Breaker(BreakerCommand),
+
+    /// Summarize PnL and execution quality from the trade journal
+    Report {
+        #[arg(long, value_enum, default_value_t = ReportPeriod::Daily)]
+        period: ReportPeriod,
+
+        /// How many periods back to report on; 0 is the current day or week so far, 1 is yesterday or last week
+        #[arg(long, default_value_t = 1)]
+        ago: u32,
+
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MarketsCommand {
+    /// Load every whitelisted market and print its top of book
+    List,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WalletCommand {
+    /// SOL and token balances of every execution wallet
+    Balances,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Load and validate the settings, then print a summary
+    Check {
+        /// Print the fully resolved settings as TOML
+        #[arg(long)]
+        print: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KeystoreCommand {
+    /// Encrypt an existing key file (JSON array, base58 or seed phrase) into the keystore
+    Import {
+        /// Plaintext key file to import
+        #[arg(long, value_name = "PATH")]
+        from: PathBuf,
+
+        /// Derivation path used when the key file holds a seed phrase
+        #[arg(long, default_value = "")]
+        derivation_path: String,
+    },
+
+    /// Decrypt the keystore into a plaintext key file
+    Export {
+        /// Destination for the plaintext key
+        #[arg(long, value_name = "PATH")]
+        to: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+    },
+
+    /// Re-encrypt the keystore under a new passphrase
+    ChangePassphrase,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SignerCommand {
+    /// Hold the key and sign requests from the bot over `security.signer.socket_path`
+    Serve,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BreakerCommand {
+    /// List paused strategies and markets
+    Status,
+
+    /// Resume a paused scope; a running bot picks the change up immediately
+    Reset {
+        /// Scope to reset, e.g. `strategy:jit` or `market:<pubkey>`; resets everything when omitted
+        #[arg(long)]
+        scope: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportPeriod {
+    /// One UTC day
+    Daily,
+    /// Seven UTC days ending with the chosen day
+    Weekly,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// `solana-keygen` compatible JSON byte array
+    Json,
+    /// Base58-encoded secret key
+    Base58,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum NetworkProfile {
+    #[value(name = "mainnet-beta", alias = "mainnet")]
+    MainnetBeta,
+    Devnet,
+    Testnet,
+    /// `solana-test-validator` on this host
+    Localnet,
+}
+
+impl NetworkProfile {
+    // Public RPC and websocket endpoints of the cluster
+    pub fn endpoints(&self) -> (&'static str, &'static str) {
+        match self {
+            NetworkProfile::MainnetBeta => ("https://api.mainnet-beta.solana.com", "wss://api.mainnet-beta.solana.com"),
+            NetworkProfile::Devnet => ("https://api.devnet.solana.com", "wss://api.devnet.solana.com"),
+            NetworkProfile::Testnet => ("https://api.testnet.solana.com", "wss://api.testnet.solana.com"),
+            NetworkProfile::Localnet => ("http://127.0.0.1:8899", "ws://127.0.0.1:8900"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LogFormat {
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl LogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_flags_become_overrides_before_set() {
+        let cli = Cli::try_parse_from([
+            "solana-arbitrage-bot",
+            "scan",
+            "--once",
+            "--network",
+            "devnet",
+            "--log-format",
+            "json",
+            "--set",
+            "network.rpc_endpoints=http://127.0.0.1:8899",
+        ])
+        .unwrap();
+
+        assert!(matches!(cli.command, Some(Command::Scan { once: true, .. })));
+        assert_eq!(
+            cli.settings_overrides(),
+            vec![
+                "network.rpc_endpoints=https://api.devnet.solana.com",
+                "network.ws_endpoints=wss://api.devnet.solana.com",
+                "network.backup_nodes=",
+                "monitoring.log_format=json",
+                "network.rpc_endpoints=http://127.0.0.1:8899",
+            ]
+        );
+
+        let cli = Cli::try_parse_from(["solana-arbitrage-bot", "simulate", "opportunity.json"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Simulate { .. })));
+        assert!(cli.settings_overrides().is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+use {
+    crate::{cli::BreakerCommand, config::Settings, risk::BreakerState, types::common::ArbitrageError},
+    std::{
+        path::Path,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+pub fn run_breaker_command(command: &BreakerCommand, settings: &Settings) -> Result<(), ArbitrageError> {
+    let path = Path::new(&settings.trading.risk.circuit_breaker.state_path);
+    let mut state = BreakerState::load(path)?;
+
+    match command {
+        BreakerCommand::Status => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+            let mut open = 0;
+            for (scope, trip) in state.active(now) {
+                println!("{}: {} ({}s left)", scope, trip.reason, trip.until - now);
+                open += 1;
+            }
+            if open == 0 {
+                println!("No strategies or markets are paused");
+            }
+        }
+        BreakerCommand::Reset { scope } => {
+            let cleared = state.reset(scope.as_deref());
+            state.save(path)?;
+            println!("Reset {} circuit breaker trip(s)", cleared);
+        }
+    }
+
+    Ok(())
+}
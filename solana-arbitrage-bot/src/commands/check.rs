@@ -0,0 +1,51 @@
+use {
+    crate::{cli::ConfigCommand, config::Settings, types::common::ArbitrageError},
+};
+
+// Settings are loaded and validated before any command runs, so reaching here means they passed
+pub fn run_config_command(command: &ConfigCommand, settings: &Settings) -> Result<(), ArbitrageError> {
+    match command {
+        ConfigCommand::Check { print } => {
+            if *print {
+                let resolved = toml::to_string_pretty(settings)
+                    .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode settings: {}", e)))?;
+                println!("{}", resolved);
+            }
+
+            let execution = &settings.trading.execution;
+            println!("Settings are valid");
+            println!(
+                "network: {} RPC endpoints, {} backups",
+                settings.network.rpc_endpoints.len(),
+                settings.network.backup_nodes.len()
+            );
+            println!(
+                "trading: strategies {}; {} whitelisted markets; min profit {:.2}%; up to {} concurrent trades",
+                execution.execution_strategies.join(", "),
+                settings.trading.markets.whitelisted_markets.len(),
+                execution.min_profit_threshold * 100.0,
+                execution.max_concurrent_trades
+            );
+            println!("signer: {}", settings.security.signer.backend);
+            println!("journal: {}", settings.monitoring.journal_path);
+            println!(
+                "metrics: {}",
+                if settings.monitoring.metrics_enabled {
+                    settings.monitoring.metrics_address.as_str()
+                } else {
+                    "disabled"
+                }
+            );
+            println!(
+                "admin API: {}",
+                if settings.security.admin.enabled {
+                    settings.security.admin.address.as_str()
+                } else {
+                    "disabled"
+                }
+            );
+        }
+    }
+
+    Ok(())
+}
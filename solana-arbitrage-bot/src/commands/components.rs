@@ -0,0 +1,104 @@
+use {
+    crate::{
+        config::{Settings, SettingsHandle},
+        core::ArbitrageEngine,
+        journal::Journal,
+        monitoring::{alert_channel, Metrics},
+        risk::{CircuitBreaker, DailyRiskManager},
+        signer::{RemoteSigner, SharedSigner},
+        types::common::ArbitrageError,
+        wallet::{load_master_keypair, ExecutionPool, WalletManager},
+    },
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signer::Signer},
+    std::{path::Path, sync::Arc},
+};
+
+// Signers for the execution pool: the remote signer, or the hot wallets derived from the master key
+pub struct ExecutionWallets {
+    pub signers: Vec<SharedSigner>,
+    pub remote: Option<Arc<RemoteSigner>>,
+    // Rotates the local hot wallets; only the running bot spawns it
+    pub manager: Option<Arc<WalletManager>>,
+}
+
+pub fn rpc_client(settings: &Settings) -> Arc<RpcClient> {
+    Arc::new(RpcClient::new_with_commitment(
+        settings.network.rpc_endpoints[0].clone(),
+        CommitmentConfig::confirmed(),
+    ))
+}
+
+pub fn open_journal(settings: &Settings) -> Result<Arc<Journal>, ArbitrageError> {
+    Ok(Arc::new(Journal::open(Path::new(&settings.monitoring.journal_path))?))
+}
+
+pub fn execution_wallets(settings: &SettingsHandle, journal: Arc<Journal>) -> Result<ExecutionWallets, ArbitrageError> {
+    let current = settings.load();
+
+    if current.security.signer.backend == "remote" {
+        let remote = Arc::new(RemoteSigner::connect(&current.security.signer)?);
+        log::info!("Using remote signer {} at {}", remote.pubkey(), current.security.signer.socket_path);
+        return Ok(ExecutionWallets {
+            signers: vec![remote.clone()],
+            remote: Some(remote),
+            manager: None,
+        });
+    }
+
+    let keypair = load_master_keypair(&current)?;
+    log::info!("Loaded keypair: {}", keypair.pubkey());
+
+    let manager = Arc::new(WalletManager::new(settings.clone(), journal, &keypair)?);
+    log::info!("Derived {} hot wallets", manager.wallets().len());
+
+    Ok(ExecutionWallets {
+        signers: manager
+            .wallets()
+            .iter()
+            .map(|wallet| wallet.clone() as SharedSigner)
+            .collect(),
+        remote: None,
+        manager: Some(manager),
+    })
+}
+
+// The engine without its trading loop or background tasks, for commands that only look.
+// `with_wallets` loads the execution wallets, which may prompt for the keystore passphrase.
+pub fn offline_engine(settings: &Settings, with_wallets: bool) -> Result<Arc<ArbitrageEngine>, ArbitrageError> {
+    let handle = SettingsHandle::new(settings.clone());
+    let rpc_client = rpc_client(settings);
+    let journal = open_journal(settings)?;
+
+    let signers = if with_wallets {
+        execution_wallets(&handle, journal.clone())?.signers
+    } else {
+        vec![]
+    };
+    let pool = Arc::new(ExecutionPool::new(handle.clone(), rpc_client, journal.clone(), signers));
+
+    // Alerts are only logged; webhooks belong to the running bot
+    let (alerts, mut alert_receiver) = alert_channel();
+    tokio::spawn(async move {
+        while let Some(alert) = alert_receiver.recv().await {
+            log::warn!("{}", alert);
+        }
+    });
+
+    let risk = Arc::new(DailyRiskManager::open(handle.clone(), Path::new(&settings.trading.risk.state_path))?);
+    let breaker = Arc::new(CircuitBreaker::open(
+        handle.clone(),
+        Path::new(&settings.trading.risk.circuit_breaker.state_path),
+        alerts.clone(),
+    )?);
+
+    Ok(Arc::new(ArbitrageEngine::new(
+        handle,
+        pool,
+        risk,
+        breaker,
+        journal,
+        Arc::new(Metrics::new()?),
+        alerts,
+    )?))
+}
@@ -0,0 +1,72 @@
+use {
+    crate::{
+        cli::{ExportFormat, KeystoreCommand},
+        config::Settings,
+        types::common::ArbitrageError,
+        wallet::{change_passphrase, load_keypair, read_passphrase, write_private_file, KeystoreFile},
+    },
+    solana_sdk::signature::Signer,
+    std::path::Path,
+};
+
+pub fn run_keystore_command(command: &KeystoreCommand, settings: &Settings) -> Result<(), ArbitrageError> {
+    let keystore = &settings.security.keystore;
+    let path = Path::new(&keystore.path);
+
+    match command {
+        KeystoreCommand::Import { from, derivation_path } => {
+            if path.exists() {
+                return Err(ArbitrageError::ConfigError(format!(
+                    "Keystore {} already exists; remove it first",
+                    path.display()
+                )));
+            }
+
+            let keypair = load_keypair(&from.to_string_lossy(), derivation_path)?;
+            let passphrase = read_new_passphrase(&keystore.passphrase_env)?;
+            KeystoreFile::encrypt(&keypair, &passphrase)?.write(path)?;
+
+            println!("Imported {} into {}", keypair.pubkey(), path.display());
+        }
+        KeystoreCommand::Export { to, format } => {
+            let passphrase = read_passphrase(&keystore.passphrase_env, "Keystore passphrase: ")?;
+            let keypair = KeystoreFile::read(path)?.decrypt(&passphrase)?;
+
+            let contents = match format {
+                ExportFormat::Json => serde_json::to_string(&keypair.to_bytes().to_vec())
+                    .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode keypair: {}", e)))?,
+                ExportFormat::Base58 => keypair.to_base58_string(),
+            };
+            write_private_file(to, contents.as_bytes())?;
+
+            println!("Exported {} to {}", keypair.pubkey(), to.display());
+        }
+        KeystoreCommand::ChangePassphrase => {
+            let old_passphrase = read_passphrase(&keystore.passphrase_env, "Current passphrase: ")?;
+            let new_passphrase = read_new_passphrase("")?;
+            change_passphrase(path, &old_passphrase, &new_passphrase)?;
+
+            println!("Passphrase changed for {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+fn read_new_passphrase(env_var: &str) -> Result<String, ArbitrageError> {
+    let interactive = env_var.is_empty() || std::env::var(env_var).is_err();
+    let passphrase = read_passphrase(env_var, "New passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(ArbitrageError::ConfigError("Passphrase must not be empty".to_string()));
+    }
+
+    // Only confirm interactively entered passphrases
+    if interactive {
+        let confirmation = read_passphrase("", "Confirm passphrase: ")?;
+        if confirmation != passphrase {
+            return Err(ArbitrageError::ConfigError("Passphrases do not match".to_string()));
+        }
+    }
+
+    Ok(passphrase)
+}
@@ -0,0 +1,40 @@
+use {
+    super::offline_engine,
+    crate::{cli::MarketsCommand, config::Settings, types::common::ArbitrageError},
+};
+
+pub async fn run_markets_command(command: &MarketsCommand, settings: &Settings) -> Result<(), ArbitrageError> {
+    match command {
+        MarketsCommand::List => {
+            let engine = offline_engine(settings, false)?;
+            let mut markets = engine.watched_markets().await?;
+            markets.sort_by_key(|state| state.market_address.to_string());
+
+            for state in &markets {
+                let mid = (state.best_bid + state.best_ask) / 2.0;
+                let spread = if mid > 0.0 {
+                    (state.best_ask - state.best_bid) / mid * 100.0
+                } else {
+                    0.0
+                };
+                println!(
+                    "{} {}/{}: bid {} ask {} ({:.3}% spread) at slot {}",
+                    state.market_address,
+                    state.base_token.symbol,
+                    state.quote_token.symbol,
+                    state.best_bid,
+                    state.best_ask,
+                    spread,
+                    state.slot
+                );
+            }
+            println!(
+                "{} of {} whitelisted markets loaded",
+                markets.len(),
+                settings.trading.markets.whitelisted_markets.len()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+mod breaker;
+mod check;
+mod components;
+mod keystore;
+mod markets;
+mod quote;
+mod report;
+mod run;
+mod scan;
+mod signer;
+mod simulate;
+mod wallet;
+
+pub use breaker::*;
+pub use check::*;
+pub use components::*;
+pub use keystore::*;
+pub use markets::*;
+pub use quote::*;
+pub use report::*;
+pub use run::*;
+pub use scan::*;
+pub use signer::*;
+pub use simulate::*;
+pub use wallet::*;
@@ -0,0 +1,46 @@
+use {
+    super::offline_engine,
+    crate::{
+        config::Settings,
+        core::ProfitCalculator,
+        types::common::{ArbitrageError, TradeSide, TradeStep},
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::{str::FromStr, sync::Arc},
+};
+
+// Quotes both directions with the same fee and slippage model the engine uses to re-quote routes
+pub async fn run_quote_command(pool: &str, amount: u64, settings: &Settings) -> Result<(), ArbitrageError> {
+    let market =
+        Pubkey::from_str(pool).map_err(|e| ArbitrageError::ConfigError(format!("Invalid pool {}: {}", pool, e)))?;
+    let engine = offline_engine(settings, false)?;
+    let state = engine.market_state(&market).await?;
+
+    let calculator = ProfitCalculator::new(Arc::new(settings.clone()));
+    let states = [state.clone()];
+    let quote = |side, price| {
+        let step = TradeStep {
+            market,
+            side,
+            amount,
+            price,
+        };
+        calculator
+            .calculate_step_profit(&step, amount as f64, &states)
+            .map(|(_, output)| output)
+    };
+    let sold = quote(TradeSide::Sell, state.best_bid)?;
+    let bought = quote(TradeSide::Buy, state.best_ask)?;
+
+    let base = &state.base_token.symbol;
+    let quote_symbol = &state.quote_token.symbol;
+    let floor = 1.0 - settings.trading.risk.slippage_tolerance;
+    println!(
+        "{} {}/{} at slot {}: bid {} ask {}",
+        market, base, quote_symbol, state.slot, state.best_bid, state.best_ask
+    );
+    println!("Sell {} {}: {:.0} {} (min {:.0})", amount, base, sold, quote_symbol, sold * floor);
+    println!("Buy with {} {}: {:.0} {} (min {:.0})", amount, quote_symbol, bought, base, bought * floor);
+
+    Ok(())
+}
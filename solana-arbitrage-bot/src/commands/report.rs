@@ -0,0 +1,185 @@
+use {
+    crate::{
+        cli::{ReportFormat, ReportPeriod},
+        config::Settings,
+        journal::{build_report, GroupStats, Journal, PnlReport},
+        types::common::ArbitrageError,
+    },
+    std::{
+        path::Path,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+pub fn run_report_command(
+    period: ReportPeriod,
+    ago: u32,
+    format: ReportFormat,
+    settings: &Settings,
+) -> Result<(), ArbitrageError> {
+    let path = Path::new(&settings.monitoring.journal_path);
+    if !path.exists() {
+        return Err(ArbitrageError::ConfigError(format!("No trade journal at {}", path.display())));
+    }
+    let journal = Journal::open(path)?;
+
+    // Windows are aligned to UTC midnight and end with the chosen day
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+    let until = (now / DAY_SECS + 1 - ago as i64) * DAY_SECS;
+    let since = match period {
+        ReportPeriod::Daily => until - DAY_SECS,
+        ReportPeriod::Weekly => until - 7 * DAY_SECS,
+    };
+
+    let execution = &settings.trading.execution;
+    let capacity = execution.max_position_size * execution.max_concurrent_trades as u64;
+    let report = build_report(
+        since,
+        until,
+        &journal.opportunities(since, until)?,
+        &journal.executions(since, until)?,
+        &journal.rebalances(since, until)?,
+        capacity,
+    );
+
+    match format {
+        ReportFormat::Table => print_table(&report),
+        ReportFormat::Csv => print_csv(&report),
+        ReportFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode report: {}", e)))?
+        ),
+    }
+
+    Ok(())
+}
+
+fn sections(report: &PnlReport) -> [(&'static str, &[GroupStats]); 4] {
+    [
+        ("strategy", &report.by_strategy[..]),
+        ("token", &report.by_token[..]),
+        ("dex", &report.by_dex[..]),
+        ("route", &report.by_route[..]),
+    ]
+}
+
+fn print_table(report: &PnlReport) {
+    let total = &report.total;
+    println!("PnL report {} .. {} (UTC)", utc_date(report.since), utc_date(report.until));
+    println!(
+        "Opportunities {}, sent {}, landed {}, hit rate {:.1}%",
+        report.opportunities,
+        total.sent,
+        total.landed,
+        total.hit_rate * 100.0
+    );
+    println!(
+        "Realized {} SOL (estimated {}), fees {}, tips {}, rebalancing {}, capital utilization {:.2}%",
+        sol(total.realized_pnl),
+        sol(total.estimated_pnl),
+        sol(total.fees as i64),
+        sol(total.tips as i64),
+        sol(report.rebalance_cost as i64),
+        total.capital_utilization * 100.0
+    );
+
+    for (dimension, groups) in sections(report) {
+        if groups.is_empty() {
+            continue;
+        }
+        println!();
+        println!(
+            "{:<44} {:>6} {:>6} {:>8} {:>14} {:>12} {:>12} {:>12} {:>12} {:>8}",
+            dimension, "sent", "landed", "hit", "realized SOL", "avg est", "avg real", "fees", "tips", "util"
+        );
+        for group in groups {
+            println!(
+                "{:<44} {:>6} {:>6} {:>7.1}% {:>14} {:>12.0} {:>12.0} {:>12} {:>12} {:>7.2}%",
+                group.key,
+                group.sent,
+                group.landed,
+                group.hit_rate * 100.0,
+                sol(group.realized_pnl),
+                group.avg_estimated,
+                group.avg_realized,
+                group.fees,
+                group.tips,
+                group.capital_utilization * 100.0
+            );
+        }
+    }
+
+    if !report.failures.is_empty() {
+        println!();
+        println!("{:>6}  {:<10} reason", "count", "stage");
+        for failure in &report.failures {
+            println!("{:>6}  {:<10} {}", failure.count, failure.stage, failure.reason);
+        }
+    }
+}
+
+fn print_csv(report: &PnlReport) {
+    println!(
+        "dimension,key,sent,landed,profitable,hit_rate,estimated_pnl,realized_pnl,avg_estimated,avg_realized,\
+         fees,tips,notional,capital_utilization"
+    );
+    let total = [report.total.clone()];
+    for (dimension, groups) in [("total", &total[..])].into_iter().chain(sections(report)) {
+        for group in groups {
+            println!(
+                "{},{},{},{},{},{:.4},{},{},{:.2},{:.2},{},{},{},{:.6}",
+                dimension,
+                csv_field(&group.key),
+                group.sent,
+                group.landed,
+                group.profitable,
+                group.hit_rate,
+                group.estimated_pnl,
+                group.realized_pnl,
+                group.avg_estimated,
+                group.avg_realized,
+                group.fees,
+                group.tips,
+                group.notional,
+                group.capital_utilization
+            );
+        }
+    }
+
+    println!();
+    println!("stage,reason,count");
+    for failure in &report.failures {
+        println!("{},{},{}", failure.stage, csv_field(&failure.reason), failure.count);
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(|c| matches!(c, ',' | '"' | '\n')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn sol(lamports: i64) -> String {
+    format!("{:.6}", lamports as f64 / 1e9)
+}
+
+// `YYYY-MM-DD` for a unix timestamp, using the days-to-civil conversion from
+// http://howardhinnant.github.io/date_algorithms.html
+fn utc_date(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(DAY_SECS) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
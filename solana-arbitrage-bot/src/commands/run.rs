@@ -0,0 +1,243 @@
+use {
+    crate::{
+        admin::{admin_channel, serve_admin},
+        commands::{execution_wallets, open_journal, rpc_client},
+        config::{Settings, SettingsHandle, SettingsWatcher},
+        core::{ArbitrageEngine, ArbitrageStrategy},
+        dex::DexRegistry,
+        inventory::{InventoryRebalancer, InventoryTracker},
+        monitoring::{
+            alert_channel, health_routes, metrics_routes, serve_http, track_wallet_balances, Alert, AlertDispatcher,
+            AlertSeverity, HealthChecks, Metrics, Watchdog,
+        },
+        risk::{CircuitBreaker, DailyRiskManager},
+        strategies::StrategyFactory,
+        types::common::ArbitrageError,
+        wallet::ExecutionPool,
+    },
+    std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::Duration,
+    },
+    tokio::signal::unix::{signal, SignalKind},
+};
+
+// Trade until a shutdown signal; `overrides` are reapplied whenever the config file is reloaded
+pub async fn run_bot(
+    settings: Settings,
+    config_path: Option<PathBuf>,
+    overrides: Vec<String>,
+) -> Result<(), ArbitrageError> {
+    log::info!("Starting Solana Arbitrage Bot...");
+
+    // Hold settings behind a swappable handle and watch the config file for changes
+    let settings_handle = SettingsHandle::new(settings.clone());
+    if let Some(config_path) = config_path {
+        let watcher = SettingsWatcher::new(settings_handle.clone(), config_path, overrides);
+        tokio::spawn(watcher.run());
+    }
+
+    // Log alerts and post them to the configured webhooks
+    let (alerts, alert_receiver) = alert_channel();
+    let dispatcher = Arc::new(AlertDispatcher::new(settings_handle.clone())?);
+    let alert_loop = dispatcher.clone();
+    tokio::spawn(async move { alert_loop.run(alert_receiver).await });
+    alerts.send(Alert::new(AlertSeverity::Info, "process", format!("Started v{}", env!("CARGO_PKG_VERSION"))));
+
+    // Open the trade journal
+    let journal = open_journal(&settings)?;
+    let rpc_client = rpc_client(&settings);
+
+    // Sign through the remote signing service, or locally with the derived hot wallet pool
+    let wallets = execution_wallets(&settings_handle, journal.clone())?;
+    let remote_signer = wallets.remote;
+
+    // Hand each concurrent trade its own wallet and rebalance inventory in the background
+    let pool = Arc::new(ExecutionPool::new(settings_handle.clone(), rpc_client, journal.clone(), wallets.signers));
+    log::info!("Execution wallet pool of {}", pool.wallets().len());
+
+    // Restore the rolling daily risk budget
+    let risk = Arc::new(DailyRiskManager::open(
+        settings_handle.clone(),
+        Path::new(&settings.trading.risk.state_path),
+    )?);
+
+    // Watch RPC health and wallet fee balances
+    tokio::spawn(Watchdog::new(settings_handle.clone(), pool.clone(), alerts.clone()).run());
+
+    // Pause failing strategies and markets, raising an alert when the breaker trips
+    let breaker = Arc::new(CircuitBreaker::open(
+        settings_handle.clone(),
+        Path::new(&settings.trading.risk.circuit_breaker.state_path),
+        alerts.clone(),
+    )?);
+
+    // Unwind inventory left behind by routes that failed partway
+    let dex = Arc::new(DexRegistry::new());
+    let inventory = Arc::new(InventoryTracker::new(settings_handle.clone(), pool.clone(), dex.clone(), alerts.clone()));
+
+    // Keep working balances of route tokens inside their target bands
+    let rebalancer = Arc::new(InventoryRebalancer::new(settings_handle.clone(), pool.clone(), dex, journal.clone()));
+
+    // Initialize arbitrage engine
+    let metrics = Arc::new(Metrics::new()?);
+    let engine = Arc::new(ArbitrageEngine::new(
+        settings_handle.clone(),
+        pool.clone(),
+        risk,
+        breaker.clone(),
+        journal,
+        metrics.clone(),
+        alerts,
+    )?);
+    log::info!("Arbitrage engine initialized");
+
+    // Background tasks that send transactions stop with the engine, so they are kept to be awaited on shutdown
+    let mut background = vec![
+        tokio::spawn(pool.clone().run(engine.shutdown_signal())),
+        tokio::spawn(inventory.clone().run(engine.shutdown_signal())),
+        tokio::spawn(rebalancer.run(engine.stop_signal(), engine.shutdown_signal())),
+    ];
+
+    // Rotate the active hot wallet, sweeping each retired one to the cold address
+    if let Some(manager) = wallets.manager {
+        background.push(tokio::spawn(manager.run(pool.clone(), engine.shutdown_signal())));
+    }
+
+    // Let operators pause strategies and markets, change thresholds and stop trading over HTTP
+    let (admin, admin_receiver) = admin_channel();
+    if settings.security.admin.enabled {
+        let admin_settings = settings.security.admin.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_admin(&admin_settings, admin, inventory).await {
+                log::error!("{}", e);
+            }
+        });
+    }
+
+    // Give background tasks the same drain deadline as in-flight trades, counted from the shutdown signal
+    let mut shutdown = engine.shutdown_signal();
+    let drain_settings = settings_handle.clone();
+    let background_stopped = tokio::spawn(async move {
+        while !*shutdown.borrow() {
+            if shutdown.changed().await.is_err() {
+                break;
+            }
+        }
+        let deadline = Duration::from_secs(drain_settings.load().trading.execution.shutdown_drain_secs);
+        tokio::time::timeout(deadline, futures::future::join_all(background)).await.is_ok()
+    });
+
+    // Serve health and readiness checks for the supervisor, and Prometheus metrics when enabled
+    let health = Arc::new(HealthChecks::new(settings_handle, engine.clone(), pool.clone(), breaker, remote_signer));
+    tokio::spawn(health.clone().run());
+    let mut router = health_routes(health);
+    if settings.monitoring.metrics_enabled {
+        router = router.merge(metrics_routes(metrics.clone()));
+        tokio::spawn(track_wallet_balances(metrics, pool));
+    }
+    let address = &settings.monitoring.metrics_address;
+    let address = address
+        .parse()
+        .map_err(|e| ArbitrageError::ConfigError(format!("Invalid metrics address {}: {}", address, e)))?;
+    tokio::spawn(async move {
+        if let Err(e) = serve_http(address, router).await {
+            log::error!("{}", e);
+        }
+    });
+
+    // Initialize strategies
+    let strategies = initialize_strategies(&settings)?;
+    log::info!("Initialized {} strategies", strategies.len());
+
+    // The first SIGINT or SIGTERM drains in-flight trades; a second exits at once
+    let draining = engine.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        log::warn!("Shutdown requested, finishing in-flight trades; signal again to exit immediately");
+        draining.shutdown();
+        shutdown_signal().await;
+        log::error!("Second shutdown signal, exiting without waiting for in-flight trades");
+        std::process::exit(130);
+    });
+
+    // Start the arbitrage bot
+    log::info!("Starting arbitrage operations...");
+    let result = engine.start(strategies, admin_receiver).await;
+
+    // Also stops the background tasks when the engine ended on an error
+    engine.shutdown();
+    match background_stopped.await {
+        Ok(true) => log::info!("Background tasks stopped"),
+        Ok(false) => log::warn!("Background tasks still running after the drain deadline; exiting without them"),
+        Err(e) => log::error!("Background shutdown task failed: {}", e),
+    }
+
+    // Sent directly so it goes out before the process exits
+    let alert = match &result {
+        Ok(()) => Alert::new(AlertSeverity::Info, "process", "Stopped after shutdown signal"),
+        Err(e) => Alert::new(AlertSeverity::Critical, "process", format!("Stopped: {}", e)),
+    };
+    dispatcher.dispatch(&alert).await;
+
+    result
+}
+
+async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            log::error!("Failed to listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    let terminate = async {
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                log::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = interrupt => {}
+        _ = terminate => {}
+    }
+}
+
+// Keyed by their `execution_strategies` name
+fn initialize_strategies(
+    settings: &Settings,
+) -> Result<Vec<(String, Box<dyn ArbitrageStrategy>)>, ArbitrageError> {
+    let mut strategies = Vec::new();
+
+    for name in ["jit", "flash_loan", "front_running"] {
+        if settings.trading.execution.execution_strategies.iter().any(|strategy| strategy == name) {
+            strategies.push((name.to_string(), StrategyFactory::create_strategy(name)?));
+        }
+    }
+
+    Ok(strategies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_strategy_initialization() {
+        let mut settings = Settings::default();
+        settings.trading.execution.execution_strategies = vec![
+            "jit".to_string(),
+            "flash_loan".to_string(),
+            "front_running".to_string(),
+        ];
+
+        let strategies = initialize_strategies(&settings).unwrap();
+        assert_eq!(strategies.len(), 3);
+    }
+}
@@ -0,0 +1,63 @@
+use {
+    super::offline_engine,
+    crate::{
+        config::Settings,
+        types::common::{ArbitrageError, ArbitrageOpportunity},
+    },
+    std::time::Duration,
+};
+
+pub async fn run_scan_command(
+    once: bool,
+    interval_ms: u64,
+    json: bool,
+    settings: &Settings,
+) -> Result<(), ArbitrageError> {
+    let engine = offline_engine(settings, false)?;
+    let mut interval = tokio::time::interval(Duration::from_millis(interval_ms.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        let opportunities = match engine.scan().await {
+            Ok(opportunities) => opportunities,
+            Err(e) if once => return Err(e),
+            Err(e) => {
+                log::error!("Scan failed: {}", e);
+                continue;
+            }
+        };
+
+        for opportunity in &opportunities {
+            if json {
+                let line = serde_json::to_string(opportunity)
+                    .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode opportunity: {}", e)))?;
+                println!("{}", line);
+            } else {
+                println!("{}", describe_opportunity(opportunity));
+            }
+        }
+        if !json {
+            println!("{} opportunities", opportunities.len());
+        }
+
+        if once {
+            return Ok(());
+        }
+    }
+}
+
+pub(super) fn describe_opportunity(opportunity: &ArbitrageOpportunity) -> String {
+    format!(
+        "{} {} -> {} {}/{}: {} in, est. profit {} ({:.3}%) at slot {}",
+        opportunity.strategy,
+        opportunity.source_market,
+        opportunity.target_market,
+        opportunity.token_pair.base_token.symbol,
+        opportunity.token_pair.quote_token.symbol,
+        opportunity.required_amount,
+        opportunity.estimated_profit,
+        opportunity.profit_percentage,
+        opportunity.slot
+    )
+}
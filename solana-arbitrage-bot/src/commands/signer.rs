@@ -0,0 +1,29 @@
+use {
+    crate::{
+        cli::SignerCommand,
+        config::Settings,
+        signer::{read_auth_secret, SignerServer, SigningPolicy},
+        types::common::ArbitrageError,
+        wallet::load_master_keypair,
+    },
+    std::{path::Path, sync::Arc},
+};
+
+pub async fn run_signer_command(command: &SignerCommand, settings: &Settings) -> Result<(), ArbitrageError> {
+    let signer = &settings.security.signer;
+
+    match command {
+        SignerCommand::Serve => {
+            let keypair = load_master_keypair(settings)?;
+            let secret = read_auth_secret(&signer.auth_secret_env)?;
+            let policy = SigningPolicy::from_settings(signer)?;
+
+            let listener = SignerServer::bind(Path::new(&signer.socket_path))?;
+            log::info!("Signer listening on {}", signer.socket_path);
+
+            Arc::new(SignerServer::new(keypair, secret, policy))
+                .serve(listener)
+                .await
+        }
+    }
+}
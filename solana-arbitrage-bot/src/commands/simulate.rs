@@ -0,0 +1,42 @@
+use {
+    super::{offline_engine, scan::describe_opportunity},
+    crate::{
+        config::Settings,
+        types::common::{ArbitrageError, ArbitrageOpportunity},
+    },
+    std::path::Path,
+};
+
+pub async fn run_simulate_command(path: &Path, settings: &Settings) -> Result<(), ArbitrageError> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to read {}: {}", path.display(), e)))?;
+    let opportunity: ArbitrageOpportunity = serde_json::from_str(&contents)
+        .map_err(|e| ArbitrageError::ConfigError(format!("{} is not an opportunity: {}", path.display(), e)))?;
+
+    let engine = offline_engine(settings, true)?;
+    let dry_run = engine.dry_run(&opportunity).await?;
+
+    println!("{}", describe_opportunity(&opportunity));
+    println!(
+        "Against current markets: {}",
+        if dry_run.profitable_now { "profitable" } else { "below min_profit_threshold" }
+    );
+    if let Some(units) = dry_run.units_consumed {
+        println!("Compute units: {}", units);
+    }
+    for line in &dry_run.logs {
+        println!("  {}", line);
+    }
+
+    // A failed simulation exits non-zero so scripts can tell
+    match dry_run.simulation_error {
+        None => {
+            println!("Simulation with wallet {} succeeded", dry_run.wallet);
+            Ok(())
+        }
+        Some(e) => Err(ArbitrageError::TransactionError(format!(
+            "Simulation with wallet {} failed: {}",
+            dry_run.wallet, e
+        ))),
+    }
+}
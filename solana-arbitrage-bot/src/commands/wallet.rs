@@ -0,0 +1,32 @@
+use {
+    super::{execution_wallets, open_journal, rpc_client},
+    crate::{
+        cli::WalletCommand,
+        config::{Settings, SettingsHandle},
+        types::common::ArbitrageError,
+        wallet::ExecutionPool,
+    },
+};
+
+pub fn run_wallet_command(command: &WalletCommand, settings: &Settings) -> Result<(), ArbitrageError> {
+    match command {
+        WalletCommand::Balances => {
+            let handle = SettingsHandle::new(settings.clone());
+            let rpc_client = rpc_client(settings);
+            let journal = open_journal(settings)?;
+            let wallets = execution_wallets(&handle, journal.clone())?;
+            let pool = ExecutionPool::new(handle, rpc_client, journal, wallets.signers);
+
+            // Trade counts live in the running bot, so only balances are shown
+            for report in pool.report()? {
+                let mut line = format!("{}: {:.4} SOL", report.pubkey, report.lamports as f64 / 1e9);
+                for (mint, amount) in &report.tokens {
+                    line.push_str(&format!(", {} {}", amount, mint));
+                }
+                println!("{}", line);
+            }
+        }
+    }
+
+    Ok(())
+}
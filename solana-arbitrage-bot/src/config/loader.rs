@@ -0,0 +1,312 @@
+use {
+    crate::{config::Settings, types::common::ArbitrageError},
+    std::{env, fs, path::Path},
+    toml::Value,
+};
+
+// Prefix for the generic per-field variables, e.g. `ARB_TRADING__EXECUTION__MAX_CONCURRENT_TRADES`
+const ENV_PREFIX: &str = "ARB_";
+
+#[derive(Debug, Clone, Copy)]
+enum EnvUnit {
+    Plain,
+    // Value is given in percent but stored as a fraction
+    Percent,
+}
+
+// Variables documented in `.env.example` and the setting each one feeds
+const ENV_ALIASES: &[(&str, &str, EnvUnit)] = &[
+    ("SOLANA_RPC_URL", "network.rpc_endpoints", EnvUnit::Plain),
+    ("SOLANA_WS_URL", "network.ws_endpoints", EnvUnit::Plain),
+    ("MAX_RETRIES", "network.max_retries", EnvUnit::Plain),
+    ("TIMEOUT_MS", "network.timeout_ms", EnvUnit::Plain),
+    ("DEV_MODE", "network.dev_mode", EnvUnit::Plain),
+    ("KEYPAIR_PATH", "wallet.keypair_path", EnvUnit::Plain),
+    ("DERIVATION_PATH", "wallet.derivation_path", EnvUnit::Plain),
+    ("COLD_WALLET_ADDRESS", "wallet.cold_address", EnvUnit::Plain),
+    ("WHITELISTED_MARKETS", "trading.markets.whitelisted_markets", EnvUnit::Plain),
+    ("WHITELISTED_TOKENS", "trading.markets.whitelisted_tokens", EnvUnit::Plain),
+    ("MIN_LIQUIDITY", "trading.markets.min_liquidity", EnvUnit::Plain),
+    ("MAX_SPREAD", "trading.markets.max_spread", EnvUnit::Plain),
+    ("MIN_PROFIT_PERCENTAGE", "trading.execution.min_profit_threshold", EnvUnit::Percent),
+    ("MAX_TRADE_SIZE", "trading.execution.max_position_size", EnvUnit::Plain),
+    ("MAX_CONCURRENT_TRADES", "trading.execution.max_concurrent_trades", EnvUnit::Plain),
+    ("USE_FLASH_LOANS", "trading.execution.flash_loan_enabled", EnvUnit::Plain),
+    ("FLASH_LOAN_SOURCES", "trading.execution.flash_loan_sources", EnvUnit::Plain),
+    ("EXECUTION_STRATEGIES", "trading.execution.execution_strategies", EnvUnit::Plain),
+    ("SIMULATE_TRADES", "trading.execution.simulate_trades", EnvUnit::Plain),
+    ("DAILY_VOLUME_LIMIT", "trading.risk.daily_volume_limit", EnvUnit::Plain),
+    ("POSITION_TIMEOUT", "trading.risk.position_timeout", EnvUnit::Plain),
+    ("MAX_SLIPPAGE", "trading.risk.slippage_tolerance", EnvUnit::Percent),
+    ("SECURITY_LEVEL", "security.level", EnvUnit::Plain),
+    ("MEV_PROTECTION", "security.mev_protection.enabled", EnvUnit::Plain),
+    ("KEYSTORE_ENABLED", "security.keystore.enabled", EnvUnit::Plain),
+    ("KEYSTORE_PATH", "security.keystore.path", EnvUnit::Plain),
+    ("SIGNER_BACKEND", "security.signer.backend", EnvUnit::Plain),
+    ("SIGNER_SOCKET_PATH", "security.signer.socket_path", EnvUnit::Plain),
+    ("REQUIRE_CONFIRMATIONS", "security.transaction_guards.require_confirmations", EnvUnit::Plain),
+    ("LOG_LEVEL", "monitoring.log_level", EnvUnit::Plain),
+    ("LOG_FORMAT", "monitoring.log_format", EnvUnit::Plain),
+    ("LOG_FILE", "monitoring.log_file", EnvUnit::Plain),
+    ("METRICS_ENABLED", "monitoring.metrics_enabled", EnvUnit::Plain),
+    ("PERFORMANCE_TRACKING", "monitoring.performance_tracking", EnvUnit::Plain),
+    ("METRICS_ADDRESS", "monitoring.metrics_address", EnvUnit::Plain),
+    ("ALERT_ENDPOINTS", "monitoring.alert_endpoints", EnvUnit::Plain),
+];
+
+pub struct SettingsLoader<'a> {
+    config_path: Option<&'a Path>,
+    cli_overrides: &'a [String],
+}
+
+impl<'a> SettingsLoader<'a> {
+    pub fn new(config_path: Option<&'a Path>, cli_overrides: &'a [String]) -> Self {
+        Self {
+            config_path,
+            cli_overrides,
+        }
+    }
+
+    pub fn load(&self) -> Result<Settings, ArbitrageError> {
+        dotenv::dotenv().ok();
+
+        // Defaults, then the config file
+        let settings = match self.config_path {
+            Some(path) => load_file(path)?,
+            None => Settings::default(),
+        };
+
+        let mut tree = Value::try_from(&settings)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to serialize settings: {}", e)))?;
+
+        // Environment variables
+        self.apply_env(&mut tree)?;
+
+        // CLI flags
+        set_overrides(&mut tree, self.cli_overrides, "--set")?;
+
+        tree.try_into()
+            .map_err(|e| ArbitrageError::ConfigError(format!("Invalid settings after overrides: {}", e)))
+    }
+
+    fn apply_env(&self, tree: &mut Value) -> Result<(), ArbitrageError> {
+        for (var, key, unit) in ENV_ALIASES {
+            if let Ok(raw) = env::var(var) {
+                set_value(tree, key, raw.trim(), *unit).map_err(|e| {
+                    ArbitrageError::ConfigError(format!("environment variable {}: {}", var, e))
+                })?;
+            }
+        }
+
+        // The generic form maps every field and takes precedence over the aliases
+        for key in leaf_keys(tree, "") {
+            let var = env_name(&key);
+            if let Ok(raw) = env::var(&var) {
+                set_value(tree, &key, raw.trim(), EnvUnit::Plain).map_err(|e| {
+                    ArbitrageError::ConfigError(format!("environment variable {}: {}", var, e))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Layers `KEY=VALUE` overrides, in the same form as `--set`, over already loaded settings
+pub fn with_overrides(settings: &Settings, overrides: &[String], origin: &str) -> Result<Settings, ArbitrageError> {
+    let mut tree = Value::try_from(settings)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to serialize settings: {}", e)))?;
+    set_overrides(&mut tree, overrides, origin)?;
+
+    tree.try_into()
+        .map_err(|e| ArbitrageError::ConfigError(format!("Invalid settings after overrides: {}", e)))
+}
+
+fn set_overrides(tree: &mut Value, overrides: &[String], origin: &str) -> Result<(), ArbitrageError> {
+    for override_arg in overrides {
+        let (key, raw) = override_arg.split_once('=').ok_or_else(|| {
+            ArbitrageError::ConfigError(format!("{} {}: expected KEY=VALUE", origin, override_arg))
+        })?;
+        set_value(tree, key.trim(), raw.trim(), EnvUnit::Plain)
+            .map_err(|e| ArbitrageError::ConfigError(format!("{} {}", origin, e)))?;
+    }
+    Ok(())
+}
+
+pub fn env_name(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "__").to_uppercase())
+}
+
+fn load_file(path: &Path) -> Result<Settings, ArbitrageError> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        ArbitrageError::ConfigError(format!("{}: failed to read config file: {}", path.display(), e))
+    })?;
+
+    toml::from_str(&contents).map_err(|e| {
+        let (line, key) = match e.span() {
+            Some(span) => locate(&contents, span.start),
+            None => (0, None),
+        };
+
+        ArbitrageError::ConfigError(match key {
+            Some(key) => format!("{}:{}: `{}`: {}", path.display(), line, key, e.message()),
+            None => format!("{}:{}: {}", path.display(), line, e.message()),
+        })
+    })
+}
+
+// Resolve a byte offset into a 1-based line number and the dotted key defined on that line
+fn locate(contents: &str, offset: usize) -> (usize, Option<String>) {
+    let offset = offset.min(contents.len());
+    let line = contents[..offset].matches('\n').count() + 1;
+
+    let mut table: Option<String> = None;
+    let mut key: Option<String> = None;
+
+    for (index, text) in contents.lines().take(line).enumerate() {
+        let text = text.trim();
+        if text.starts_with('[') {
+            table = Some(text.trim_matches(|c| c == '[' || c == ']').trim().to_string());
+            key = None;
+        } else if index + 1 == line {
+            key = text
+                .split_once('=')
+                .map(|(name, _)| name.trim().trim_matches('"').to_string());
+        }
+    }
+
+    let path = match (table, key) {
+        (Some(table), Some(key)) => Some(format!("{}.{}", table, key)),
+        (Some(table), None) => Some(table),
+        (None, key) => key,
+    };
+
+    (line, path)
+}
+
+fn leaf_keys(tree: &Value, prefix: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+
+    if let Some(table) = tree.as_table() {
+        for (name, value) in table {
+            let key = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+
+            if value.is_table() {
+                keys.extend(leaf_keys(value, &key));
+            } else {
+                keys.push(key);
+            }
+        }
+    }
+
+    keys
+}
+
+fn set_value(tree: &mut Value, key: &str, raw: &str, unit: EnvUnit) -> Result<(), String> {
+    let mut node = tree;
+    let mut parts = key.split('.').peekable();
+
+    while let Some(part) = parts.next() {
+        let child = node
+            .get_mut(part)
+            .ok_or_else(|| format!("unknown setting key `{}`", key))?;
+
+        if parts.peek().is_none() {
+            *child = parse_like(child, raw, unit).map_err(|e| format!("`{}`: {}", key, e))?;
+            return Ok(());
+        }
+
+        if !child.is_table() {
+            return Err(format!("unknown setting key `{}`", key));
+        }
+        node = child;
+    }
+
+    Err(format!("unknown setting key `{}`", key))
+}
+
+// Parse a raw string into the same TOML type as the value it replaces
+fn parse_like(existing: &Value, raw: &str, unit: EnvUnit) -> Result<Value, String> {
+    match existing {
+        Value::String(_) => Ok(Value::String(raw.to_string())),
+        Value::Integer(_) => raw
+            .parse::<i64>()
+            .map(Value::Integer)
+            .map_err(|_| format!("expected an integer, got `{}`", raw)),
+        Value::Float(_) => {
+            let value = raw
+                .parse::<f64>()
+                .map_err(|_| format!("expected a number, got `{}`", raw))?;
+            Ok(Value::Float(match unit {
+                EnvUnit::Plain => value,
+                EnvUnit::Percent => value / 100.0,
+            }))
+        }
+        Value::Boolean(_) => raw
+            .parse::<bool>()
+            .map(Value::Boolean)
+            .map_err(|_| format!("expected true or false, got `{}`", raw)),
+        Value::Array(_) => Ok(Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        )),
+        _ => Err("cannot be overridden from a single value".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("arb-settings-{}-{}.toml", name, std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_partial_file_keeps_defaults() {
+        let path = write_config(
+            "partial",
+            "[trading.execution]\nmax_concurrent_trades = 7\n",
+        );
+
+        let settings = load_file(&path).unwrap();
+        assert_eq!(settings.trading.execution.max_concurrent_trades, 7);
+        assert_eq!(settings.trading.markets.min_liquidity, 1000000);
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_file_error_reports_line_and_key() {
+        let path = write_config(
+            "invalid",
+            "[network]\nmax_retries = 3\n\n[trading.execution]\nmax_concurrent_trades = \"many\"\n",
+        );
+
+        let error = load_file(&path).unwrap_err().to_string();
+        assert!(error.contains(":5:"), "{}", error);
+        assert!(error.contains("trading.execution.max_concurrent_trades"), "{}", error);
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_cli_override_parses_by_type() {
+        let mut tree = Value::try_from(&Settings::default()).unwrap();
+        set_value(&mut tree, "trading.markets.whitelisted_tokens", "a, b", EnvUnit::Plain).unwrap();
+        set_value(&mut tree, "trading.execution.min_profit_threshold", "1.5", EnvUnit::Percent).unwrap();
+        assert!(set_value(&mut tree, "trading.nope", "1", EnvUnit::Plain).is_err());
+
+        let settings: Settings = tree.try_into().unwrap();
+        assert_eq!(settings.trading.markets.whitelisted_tokens, vec!["a", "b"]);
+        assert!((settings.trading.execution.min_profit_threshold - 0.015).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,310 @@
+use {
+    crate::{
+        config::{loader::with_overrides, Settings},
+        types::common::ArbitrageError,
+    },
+    std::{
+        collections::BTreeMap,
+        fmt,
+        path::PathBuf,
+        sync::{Arc, RwLock},
+        time::{Duration, SystemTime},
+    },
+    toml::Value,
+};
+
+// Sections that are wired into long-lived connections and keys at startup
+const RESTART_REQUIRED: &[&str] = &[
+    "network",
+    "wallet",
+    "security.keystore",
+    "security.signer",
+    "security.admin",
+    "trading.markets.max_snapshot_slot_spread",
+    "trading.inventory.scan_interval_ms",
+    "trading.inventory.rebalance_interval_ms",
+    "trading.risk.state_path",
+    "trading.risk.circuit_breaker.state_path",
+    "monitoring.metrics_enabled",
+    "monitoring.log_level",
+    "monitoring.log_format",
+    "monitoring.log_file",
+    "monitoring.journal_path",
+    "monitoring.metrics_address",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingChange {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl fmt::Display for SettingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.key,
+            self.old.as_deref().unwrap_or("<unset>"),
+            self.new.as_deref().unwrap_or("<unset>")
+        )
+    }
+}
+
+// Shared handle to the live settings; readers take a cheap snapshot per use
+#[derive(Clone)]
+pub struct SettingsHandle {
+    current: Arc<RwLock<Arc<Settings>>>,
+    // Values set at runtime through the admin API, by key; reloads layer them over the file until restart
+    overrides: Arc<RwLock<BTreeMap<String, String>>>,
+}
+
+impl SettingsHandle {
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(settings))),
+            overrides: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn load(&self) -> Arc<Settings> {
+        self.current.read().unwrap().clone()
+    }
+
+    // Applies `KEY=VALUE` overrides to the live settings and keeps them in force across file reloads
+    pub fn apply_overrides(&self, overrides: &[String]) -> Result<Vec<SettingChange>, ArbitrageError> {
+        let settings = with_overrides(&self.load(), overrides, "override")?;
+        let changes = self.apply(settings)?;
+
+        let mut pinned = self.overrides.write().unwrap();
+        for override_arg in overrides {
+            if let Some((key, raw)) = override_arg.split_once('=') {
+                pinned.insert(key.trim().to_string(), raw.trim().to_string());
+            }
+        }
+
+        Ok(changes)
+    }
+
+    pub fn overrides(&self) -> Vec<String> {
+        self.overrides
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(key, raw)| format!("{}={}", key, raw))
+            .collect()
+    }
+
+    pub fn apply(&self, settings: Settings) -> Result<Vec<SettingChange>, ArbitrageError> {
+        settings.validate()?;
+
+        let mut current = self.current.write().unwrap();
+        let changes = diff_settings(&current, &settings)?;
+
+        let restart_keys: Vec<&str> = changes
+            .iter()
+            .map(|change| change.key.as_str())
+            .filter(|key| requires_restart(key))
+            .collect();
+        if !restart_keys.is_empty() {
+            return Err(ArbitrageError::ConfigError(format!(
+                "Changes to {} require a restart; reload rejected",
+                restart_keys.join(", ")
+            )));
+        }
+
+        if !changes.is_empty() {
+            *current = Arc::new(settings);
+        }
+
+        Ok(changes)
+    }
+}
+
+pub struct SettingsWatcher {
+    handle: SettingsHandle,
+    path: PathBuf,
+    cli_overrides: Vec<String>,
+    poll_interval: Duration,
+}
+
+impl SettingsWatcher {
+    pub fn new(handle: SettingsHandle, path: PathBuf, cli_overrides: Vec<String>) -> Self {
+        Self {
+            handle,
+            path,
+            cli_overrides,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+
+    pub async fn run(self) {
+        let mut last_modified = self.modified_at();
+        let mut interval = tokio::time::interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+
+            let modified = self.modified_at();
+            if modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            log::info!("Settings file {} changed, reloading", self.path.display());
+            if let Err(e) = self.reload() {
+                log::error!("Keeping previous settings: {}", e);
+            }
+        }
+    }
+
+    fn reload(&self) -> Result<(), ArbitrageError> {
+        // Re-apply the same layers the process started with so env and CLI overrides still win
+        let loaded = Settings::load(Some(&self.path), &self.cli_overrides)?;
+        let settings = self.pin_overrides(&loaded)?;
+        let changes = self.handle.apply(settings)?;
+
+        if changes.is_empty() {
+            log::info!("Settings reloaded with no effective changes");
+        }
+        for change in &changes {
+            log::info!("Setting changed: {}", change);
+        }
+
+        Ok(())
+    }
+
+    // Admin overrides win over the file; say so whenever the file asks for something else
+    fn pin_overrides(&self, loaded: &Settings) -> Result<Settings, ArbitrageError> {
+        let settings = with_overrides(loaded, &self.handle.overrides(), "admin override")?;
+        for shadowed in diff_settings(loaded, &settings)? {
+            log::warn!(
+                "Setting {} stays at {} as set through the admin API; the file value {} applies after a restart",
+                shadowed.key,
+                shadowed.new.as_deref().unwrap_or("<unset>"),
+                shadowed.old.as_deref().unwrap_or("<unset>")
+            );
+        }
+        Ok(settings)
+    }
+
+    fn modified_at(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path).and_then(|meta| meta.modified()).ok()
+    }
+}
+
+fn requires_restart(key: &str) -> bool {
+    RESTART_REQUIRED
+        .iter()
+        .any(|section| key == *section || key.starts_with(&format!("{}.", section)))
+}
+
+pub fn diff_settings(old: &Settings, new: &Settings) -> Result<Vec<SettingChange>, ArbitrageError> {
+    let old = flatten_settings(old)?;
+    let new = flatten_settings(new)?;
+
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    Ok(keys
+        .into_iter()
+        .filter(|key| old.get(*key) != new.get(*key))
+        .map(|key| SettingChange {
+            key: key.clone(),
+            old: old.get(key).cloned(),
+            new: new.get(key).cloned(),
+        })
+        .collect())
+}
+
+fn flatten_settings(settings: &Settings) -> Result<BTreeMap<String, String>, ArbitrageError> {
+    let tree = Value::try_from(settings)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to serialize settings: {}", e)))?;
+
+    let mut leaves = BTreeMap::new();
+    flatten_value(&tree, "", &mut leaves);
+    Ok(leaves)
+}
+
+fn flatten_value(value: &Value, prefix: &str, leaves: &mut BTreeMap<String, String>) {
+    match value.as_table() {
+        Some(table) => {
+            for (name, child) in table {
+                let key = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                flatten_value(child, &key, leaves);
+            }
+        }
+        None => {
+            leaves.insert(prefix.to_string(), value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_swaps_trading_changes() {
+        let handle = SettingsHandle::new(Settings::default());
+        let mut updated = Settings::default();
+        updated.trading.execution.min_profit_threshold = 0.02;
+
+        let changes = handle.apply(updated).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "trading.execution.min_profit_threshold");
+        assert_eq!(handle.load().trading.execution.min_profit_threshold, 0.02);
+    }
+
+    #[test]
+    fn test_apply_rejects_network_changes() {
+        let handle = SettingsHandle::new(Settings::default());
+        let mut updated = Settings::default();
+        updated.network.rpc_endpoints = vec!["https://example.invalid".to_string()];
+        updated.trading.execution.min_profit_threshold = 0.02;
+
+        assert!(handle.apply(updated).is_err());
+        assert_eq!(handle.load().trading.execution.min_profit_threshold, 0.01);
+    }
+
+    #[test]
+    fn test_reload_keeps_admin_overrides() {
+        let path = std::env::temp_dir().join(format!("arb-reload-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[trading.execution]\nmin_profit_threshold = 0.03\nmax_position_size = 500000000\n",
+        )
+        .unwrap();
+
+        let handle = SettingsHandle::new(Settings::default());
+        let changes = handle
+            .apply_overrides(&["trading.execution.min_profit_threshold=0.05".to_string()])
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+
+        let watcher = SettingsWatcher::new(handle.clone(), path.clone(), Vec::new());
+        let reloaded = watcher.reload();
+        std::fs::remove_file(&path).ok();
+        reloaded.unwrap();
+
+        // The file's other changes land, but the admin value is not reverted
+        let settings = handle.load();
+        assert_eq!(settings.trading.execution.min_profit_threshold, 0.05);
+        assert_eq!(settings.trading.execution.max_position_size, 500_000_000);
+    }
+
+    #[test]
+    fn test_apply_rejects_startup_only_intervals() {
+        let handle = SettingsHandle::new(Settings::default());
+        let mut updated = Settings::default();
+        updated.trading.inventory.scan_interval_ms += 1;
+
+        assert!(handle.apply(updated).is_err());
+    }
+}
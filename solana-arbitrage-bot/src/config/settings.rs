@@ -1,43 +1,74 @@
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
+use std::path::Path;
 use std::str::FromStr;
-use crate::types::common::{SecurityLevel, ArbitrageError};
+use crate::monitoring::Webhook;
+use crate::strategies::STRATEGY_TYPES;
+use crate::types::common::{SecurityLevel, ArbitrageError, FlashLoanProtocol};
+use super::loader::SettingsLoader;
+
+// `local` signs in-process; `remote` asks a separate `signer serve` process over a Unix socket
+pub const SIGNER_BACKENDS: &[&str] = &["local", "remote"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Settings {
     pub network: NetworkSettings,
+    pub wallet: WalletSettings,
     pub trading: TradingSettings,
     pub security: SecuritySettings,
     pub monitoring: MonitoringSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct NetworkSettings {
     pub rpc_endpoints: Vec<String>,
     pub ws_endpoints: Vec<String>,
     pub backup_nodes: Vec<String>,
     pub max_retries: u32,
     pub timeout_ms: u64,
+    pub dev_mode: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WalletSettings {
+    pub keypair_path: String,
+    pub derivation_path: String,
+    pub key_rotation_interval: u64,
+    pub pool_size: u32,
+    pub cold_address: String,
+    pub sweep_floor_lamports: u64,
+    pub fee_reserve_lamports: u64,
+    pub rebalance_interval: u64,
+    pub rebalance_threshold: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TradingSettings {
     pub markets: MarketSettings,
     pub execution: ExecutionSettings,
     pub risk: RiskSettings,
+    pub inventory: InventorySettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MarketSettings {
     pub whitelisted_markets: Vec<String>,
     pub whitelisted_tokens: Vec<String>,
     pub blacklisted_markets: Vec<String>,
     pub min_liquidity: u64,
     pub max_spread: f64,
+    pub max_state_age_slots: u64,
+    pub max_snapshot_slot_spread: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ExecutionSettings {
     pub max_concurrent_trades: u32,
     pub min_profit_threshold: f64,
@@ -45,25 +76,105 @@ pub struct ExecutionSettings {
     pub flash_loan_enabled: bool,
     pub flash_loan_sources: Vec<String>,
     pub execution_strategies: Vec<String>,
+    pub max_opportunity_age_slots: FreshnessSettings,
+    pub simulate_trades: bool,
+    pub account_lock_timeout_ms: u64,
+    // Accounts that receive block-engine tips; payments to them are counted as tips in realized PnL
+    pub tip_accounts: Vec<String>,
+    // On shutdown, wait at most this long for in-flight trades to land or fail
+    pub shutdown_drain_secs: u64,
+}
+
+// Maximum age, in slots, an opportunity may reach before it is discarded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FreshnessSettings {
+    pub direct: u64,
+    pub triangular: u64,
+    pub jit: u64,
+    pub flash_loan: u64,
+    pub front_running: u64,
+}
+
+impl FreshnessSettings {
+    // Factory strategies, keyed `factory:<name>`, share the window of their type
+    pub fn for_strategy(&self, strategy: &str) -> u64 {
+        match strategy.trim_start_matches("factory:") {
+            "direct" => self.direct,
+            "triangular" => self.triangular,
+            "jit" => self.jit,
+            "flash_loan" => self.flash_loan,
+            "front_running" => self.front_running,
+            // Unknown strategies get the strictest window
+            _ => self.strictest(),
+        }
+    }
+
+    fn strictest(&self) -> u64 {
+        [self.direct, self.triangular, self.jit, self.flash_loan, self.front_running]
+            .into_iter()
+            .min()
+            .unwrap_or(0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct RiskSettings {
     pub max_loss_threshold: f64,
     pub daily_volume_limit: u64,
     pub position_timeout: u64,
     pub slippage_tolerance: f64,
+    pub state_path: String,
+    pub circuit_breaker: CircuitBreakerSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CircuitBreakerSettings {
+    pub max_consecutive_failures: u32,
+    pub max_single_loss: u64,
+    pub max_profit_divergence: f64,
+    pub cooldown_secs: u64,
+    pub state_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InventorySettings {
+    pub base_mint: String,
+    pub dust_amount: u64,
+    pub max_unwind_slippage: f64,
+    pub scan_interval_ms: u64,
+    pub targets: Vec<TokenTarget>,
+    pub max_rebalance_slippage: f64,
+    pub rebalance_interval_ms: u64,
+    pub quiet_period_secs: u64,
+}
+
+// Working balance kept in every execution wallet, in raw token units
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenTarget {
+    pub mint: String,
+    pub target: u64,
+    pub min: u64,
+    pub max: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SecuritySettings {
     pub level: SecurityLevel,
     pub mev_protection: MevProtectionSettings,
-    pub quantum_security: QuantumSecuritySettings,
+    pub keystore: KeystoreSettings,
+    pub signer: SignerSettings,
     pub transaction_guards: TransactionGuardSettings,
+    pub admin: AdminSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MevProtectionSettings {
     pub enabled: bool,
     pub protection_level: u32,
@@ -73,13 +184,37 @@ pub struct MevProtectionSettings {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QuantumSecuritySettings {
+#[serde(default, deny_unknown_fields)]
+pub struct KeystoreSettings {
     pub enabled: bool,
-    pub encryption_level: String,
-    pub key_rotation_interval: u64,
+    pub path: String,
+    pub passphrase_env: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignerSettings {
+    pub backend: String,
+    pub socket_path: String,
+    pub auth_secret_env: String,
+    pub timeout_ms: u64,
+    pub allowed_programs: Vec<String>,
+    pub max_transfer_lamports: u64,
+    pub max_token_amount: u64,
+}
+
+// Local HTTP API for pausing strategies and markets, adjusting thresholds and emergency stops
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdminSettings {
+    pub enabled: bool,
+    pub address: String,
+    // Environment variable holding the bearer token every request must carry
+    pub token_env: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TransactionGuardSettings {
     pub signature_verification: bool,
     pub timeout_ms: u64,
@@ -88,17 +223,35 @@ pub struct TransactionGuardSettings {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MonitoringSettings {
     pub log_level: String,
+    // `text` or `json` for the lines written to stderr
+    pub log_format: String,
+    // JSON lines with span timings; empty to log to stderr only
+    pub log_file: String,
     pub metrics_enabled: bool,
     pub alert_endpoints: Vec<String>,
     pub performance_tracking: bool,
+    pub journal_path: String,
+    // Where `/healthz`, `/readyz` and, with `metrics_enabled`, `/metrics` are served
+    pub metrics_address: String,
+    // `/readyz` fails once the newest market data is this many slots behind
+    pub health_max_market_lag_slots: u64,
+    // Identical alerts within this window are sent once
+    pub alert_dedupe_secs: u64,
+    // Most alerts a single source may send per minute
+    pub alert_rate_limit: u32,
+    // Alert when one trade realizes a loss of at least this many lamports
+    pub large_loss_alert_lamports: u64,
+    // Prometheus text written on shutdown so the final counters outlive the process; empty to skip
+    pub metrics_snapshot_path: String,
 }
 
 impl Settings {
-    pub fn load() -> Result<Self, ArbitrageError> {
-        // Load from environment or config file
-        let settings = Self::default();
+    pub fn load(config_path: Option<&Path>, cli_overrides: &[String]) -> Result<Self, ArbitrageError> {
+        // Layer defaults, config file, environment and CLI flags in that order
+        let settings = SettingsLoader::new(config_path, cli_overrides).load()?;
         
         // Validate settings
         settings.validate()?;
@@ -106,86 +259,486 @@ impl Settings {
         Ok(settings)
     }
 
-    fn validate(&self) -> Result<(), ArbitrageError> {
+    pub fn validate(&self) -> Result<(), ArbitrageError> {
+        let errors = self.validation_errors();
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(ArbitrageError::ConfigError(format!(
+            "{} invalid setting(s):\n  - {}",
+            errors.len(),
+            errors.join("\n  - ")
+        )))
+    }
+
+    // Run every check and report all failures rather than stopping at the first
+    pub fn validation_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
         // Validate network settings
         if self.network.rpc_endpoints.is_empty() {
-            return Err(ArbitrageError::ConfigError("No RPC endpoints configured".to_string()));
+            errors.push("network.rpc_endpoints: no RPC endpoints configured".to_string());
+        }
+        for endpoint in self.network.rpc_endpoints.iter().chain(&self.network.backup_nodes) {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                errors.push(format!("network.rpc_endpoints: `{}` is not an http(s) URL", endpoint));
+            }
+        }
+        for endpoint in &self.network.ws_endpoints {
+            if !endpoint.starts_with("ws://") && !endpoint.starts_with("wss://") {
+                errors.push(format!("network.ws_endpoints: `{}` is not a ws(s) URL", endpoint));
+            }
+        }
+        if self.network.timeout_ms == 0 {
+            errors.push("network.timeout_ms: must be greater than zero".to_string());
         }
 
-        // Validate trading settings
-        if self.trading.execution.min_profit_threshold <= 0.0 {
-            return Err(ArbitrageError::ConfigError("Invalid profit threshold".to_string()));
+        // Validate wallet settings
+        if self.wallet.pool_size == 0 {
+            errors.push("wallet.pool_size: must be at least 1".to_string());
+        }
+        if !self.wallet.cold_address.is_empty() && Pubkey::from_str(&self.wallet.cold_address).is_err() {
+            errors.push(format!("wallet.cold_address: `{}` is not a valid pubkey", self.wallet.cold_address));
+        }
+        check_fraction(&mut errors, "wallet.rebalance_threshold", self.wallet.rebalance_threshold);
+
+        // Validate market settings
+        let markets = &self.trading.markets;
+        check_pubkeys(&mut errors, "trading.markets.whitelisted_markets", &markets.whitelisted_markets);
+        check_pubkeys(&mut errors, "trading.markets.whitelisted_tokens", &markets.whitelisted_tokens);
+        check_pubkeys(&mut errors, "trading.markets.blacklisted_markets", &markets.blacklisted_markets);
+        for market in &markets.whitelisted_markets {
+            if markets.blacklisted_markets.contains(market) {
+                errors.push(format!("trading.markets: `{}` is both whitelisted and blacklisted", market));
+            }
+        }
+        check_fraction(&mut errors, "trading.markets.max_spread", markets.max_spread);
+
+        // Validate execution settings
+        let execution = &self.trading.execution;
+        if execution.max_concurrent_trades == 0 {
+            errors.push("trading.execution.max_concurrent_trades: must be at least 1".to_string());
+        }
+        if execution.min_profit_threshold <= 0.0 {
+            errors.push(format!(
+                "trading.execution.min_profit_threshold: must be positive, got {}",
+                execution.min_profit_threshold
+            ));
+        } else {
+            check_fraction(&mut errors, "trading.execution.min_profit_threshold", execution.min_profit_threshold);
+        }
+        if execution.account_lock_timeout_ms == 0 {
+            errors.push("trading.execution.account_lock_timeout_ms: must be greater than zero".to_string());
+        }
+        check_pubkeys(&mut errors, "trading.execution.tip_accounts", &execution.tip_accounts);
+        if execution.max_position_size == 0 {
+            errors.push("trading.execution.max_position_size: must be greater than zero".to_string());
+        }
+        for strategy in &execution.execution_strategies {
+            if !STRATEGY_TYPES.contains(&strategy.as_str()) {
+                errors.push(format!(
+                    "trading.execution.execution_strategies: unknown strategy `{}` (expected one of {})",
+                    strategy,
+                    STRATEGY_TYPES.join(", ")
+                ));
+            }
+        }
+        for source in &execution.flash_loan_sources {
+            if FlashLoanProtocol::from_str(source).is_err() {
+                errors.push(format!(
+                    "trading.execution.flash_loan_sources: unknown flash loan source `{}`",
+                    source
+                ));
+            }
+        }
+        if execution.flash_loan_enabled && execution.flash_loan_sources.is_empty() {
+            errors.push(
+                "trading.execution.flash_loan_sources: flash loans are enabled but no source is configured".to_string(),
+            );
+        }
+
+        // Validate risk settings
+        let risk = &self.trading.risk;
+        if risk.max_loss_threshold < 0.0 {
+            errors.push(format!(
+                "trading.risk.max_loss_threshold: must be a positive loss fraction, got {}",
+                risk.max_loss_threshold
+            ));
+        } else {
+            check_fraction(&mut errors, "trading.risk.max_loss_threshold", risk.max_loss_threshold);
+        }
+        check_fraction(&mut errors, "trading.risk.slippage_tolerance", risk.slippage_tolerance);
+        if risk.state_path.is_empty() {
+            errors.push("trading.risk.state_path: required to persist the daily risk budget".to_string());
+        }
+        let breaker = &risk.circuit_breaker;
+        if breaker.max_consecutive_failures == 0 {
+            errors.push("trading.risk.circuit_breaker.max_consecutive_failures: must be at least 1".to_string());
+        }
+        if breaker.max_profit_divergence <= 0.0 {
+            errors.push(format!(
+                "trading.risk.circuit_breaker.max_profit_divergence: must be positive, got {}",
+                breaker.max_profit_divergence
+            ));
+        }
+        if breaker.state_path.is_empty() {
+            errors.push("trading.risk.circuit_breaker.state_path: required to share trips with the CLI".to_string());
+        }
+        if risk.position_timeout == 0 {
+            errors.push("trading.risk.position_timeout: must be greater than zero".to_string());
+        }
+
+        // Validate inventory settings
+        let inventory = &self.trading.inventory;
+        if Pubkey::from_str(&inventory.base_mint).is_err() {
+            errors.push(format!("trading.inventory.base_mint: `{}` is not a valid pubkey", inventory.base_mint));
+        }
+        check_fraction(&mut errors, "trading.inventory.max_unwind_slippage", inventory.max_unwind_slippage);
+        if inventory.scan_interval_ms == 0 {
+            errors.push("trading.inventory.scan_interval_ms: must be greater than zero".to_string());
+        }
+        check_fraction(&mut errors, "trading.inventory.max_rebalance_slippage", inventory.max_rebalance_slippage);
+        if inventory.rebalance_interval_ms == 0 {
+            errors.push("trading.inventory.rebalance_interval_ms: must be greater than zero".to_string());
+        }
+        let mut target_mints = Vec::new();
+        for target in &inventory.targets {
+            if Pubkey::from_str(&target.mint).is_err() {
+                errors.push(format!("trading.inventory.targets: `{}` is not a valid pubkey", target.mint));
+            }
+            if target.mint == inventory.base_mint {
+                errors.push(format!("trading.inventory.targets: `{}` is the base mint", target.mint));
+            }
+            if target_mints.contains(&&target.mint) {
+                errors.push(format!("trading.inventory.targets: `{}` is listed twice", target.mint));
+            }
+            target_mints.push(&target.mint);
+            if !(target.min <= target.target && target.target <= target.max) {
+                errors.push(format!(
+                    "trading.inventory.targets: `{}` needs min <= target <= max, got {} / {} / {}",
+                    target.mint, target.min, target.target, target.max
+                ));
+            }
+        }
+
+        // Validate cross-field constraints
+        if execution.max_position_size > risk.daily_volume_limit {
+            errors.push(format!(
+                "trading.execution.max_position_size ({}) exceeds trading.risk.daily_volume_limit ({})",
+                execution.max_position_size, risk.daily_volume_limit
+            ));
         }
 
         // Validate security settings
         if self.security.mev_protection.enabled && self.security.mev_protection.protection_level == 0 {
-            return Err(ArbitrageError::ConfigError("Invalid MEV protection level".to_string()));
+            errors.push("security.mev_protection.protection_level: must be at least 1 when enabled".to_string());
+        }
+        if self.security.keystore.enabled && self.security.keystore.path.is_empty() {
+            errors.push("security.keystore.path: required when the keystore is enabled".to_string());
+        }
+        let signer = &self.security.signer;
+        if !SIGNER_BACKENDS.contains(&signer.backend.as_str()) {
+            errors.push(format!(
+                "security.signer.backend: unknown backend `{}` (expected one of {})",
+                signer.backend,
+                SIGNER_BACKENDS.join(", ")
+            ));
+        }
+        if signer.backend == "remote" && signer.socket_path.is_empty() {
+            errors.push("security.signer.socket_path: required for the remote signer".to_string());
+        }
+        if signer.timeout_ms == 0 {
+            errors.push("security.signer.timeout_ms: must be greater than zero".to_string());
+        }
+        check_pubkeys(&mut errors, "security.signer.allowed_programs", &signer.allowed_programs);
+        if self.security.transaction_guards.timeout_ms == 0 {
+            errors.push("security.transaction_guards.timeout_ms: must be greater than zero".to_string());
+        }
+        let admin = &self.security.admin;
+        match admin.address.parse::<std::net::SocketAddr>() {
+            Ok(address) if admin.enabled && !address.ip().is_loopback() => {
+                errors.push(format!("security.admin.address: `{}` must be a loopback address", admin.address));
+            }
+            Ok(_) => {}
+            Err(_) => errors.push(format!(
+                "security.admin.address: `{}` is not a host:port socket address",
+                admin.address
+            )),
         }
 
-        Ok(())
+        // Validate monitoring settings
+        if tracing_subscriber::EnvFilter::try_new(&self.monitoring.log_level).is_err() {
+            errors.push(format!("monitoring.log_level: invalid level or filter `{}`", self.monitoring.log_level));
+        }
+        if !["text", "json"].contains(&self.monitoring.log_format.as_str()) {
+            errors.push(format!("monitoring.log_format: expected text or json, got `{}`", self.monitoring.log_format));
+        }
+        for endpoint in &self.monitoring.alert_endpoints {
+            if let Err(ArbitrageError::ConfigError(message)) = Webhook::parse(endpoint) {
+                errors.push(format!("monitoring.alert_endpoints: {}", message));
+            }
+        }
+        if self.monitoring.alert_rate_limit == 0 {
+            errors.push("monitoring.alert_rate_limit: must be greater than zero".to_string());
+        }
+        if self.monitoring.metrics_address.parse::<std::net::SocketAddr>().is_err() {
+            errors.push(format!(
+                "monitoring.metrics_address: `{}` is not a host:port socket address",
+                self.monitoring.metrics_address
+            ));
+        }
+
+        errors
+    }
+}
+
+fn check_pubkeys(errors: &mut Vec<String>, key: &str, values: &[String]) {
+    for value in values {
+        if Pubkey::from_str(value).is_err() {
+            errors.push(format!("{}: `{}` is not a valid pubkey", key, value));
+        }
+    }
+}
+
+// Fractions are stored as 0.01 for 1%; a value of 1 or more almost always means a percentage was entered
+fn check_fraction(errors: &mut Vec<String>, key: &str, value: f64) {
+    if !(0.0..1.0).contains(&value) {
+        errors.push(format!(
+            "{}: expected a fraction between 0 and 1 (0.01 = 1%), got {}",
+            key, value
+        ));
     }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Settings {
-            network: NetworkSettings {
-                rpc_endpoints: vec![std::env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.devnet.solana.com".to_string())],
-                ws_endpoints: vec![],
-                backup_nodes: vec![],
-                max_retries: 3,
-                timeout_ms: 30000,
-            },
-            trading: TradingSettings {
-                markets: MarketSettings {
-                    whitelisted_markets: vec![],
-                    whitelisted_tokens: vec![],
-                    blacklisted_markets: vec![],
-                    min_liquidity: 1000000,
-                    max_spread: 0.05,
-                },
-                execution: ExecutionSettings {
-                    max_concurrent_trades: 3,
-                    min_profit_threshold: 0.01,
-                    max_position_size: 1000000000,
-                    flash_loan_enabled: true,
-                    flash_loan_sources: vec!["solend".to_string(), "port".to_string()],
-                    execution_strategies: vec!["jit".to_string(), "flash_loan".to_string()],
-                },
-                risk: RiskSettings {
-                    max_loss_threshold: -0.02,
-                    daily_volume_limit: 1000000000000,
-                    position_timeout: 30000,
-                    slippage_tolerance: 0.01,
-                },
-            },
-            security: SecuritySettings {
-                level: SecurityLevel::High,
-                mev_protection: MevProtectionSettings {
-                    enabled: true,
-                    protection_level: 2,
-                    sandwich_detection: true,
-                    frontrunning_detection: true,
-                    backrunning_detection: true,
-                },
-                quantum_security: QuantumSecuritySettings {
-                    enabled: true,
-                    encryption_level: "AES-256".to_string(),
-                    key_rotation_interval: 3600,
-                },
-                transaction_guards: TransactionGuardSettings {
-                    signature_verification: true,
-                    timeout_ms: 5000,
-                    max_retries: 3,
-                    require_confirmations: 1,
-                },
-            },
-            monitoring: MonitoringSettings {
-                log_level: "info".to_string(),
-                metrics_enabled: true,
-                alert_endpoints: vec![],
-                performance_tracking: true,
-            },
+            network: NetworkSettings::default(),
+            wallet: WalletSettings::default(),
+            trading: TradingSettings::default(),
+            security: SecuritySettings::default(),
+            monitoring: MonitoringSettings::default(),
+        }
+    }
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        NetworkSettings {
+            rpc_endpoints: vec!["https://api.devnet.solana.com".to_string()],
+            ws_endpoints: vec![],
+            backup_nodes: vec![],
+            max_retries: 3,
+            timeout_ms: 30000,
+            dev_mode: false,
+        }
+    }
+}
+
+impl Default for WalletSettings {
+    fn default() -> Self {
+        WalletSettings {
+            keypair_path: "keypair.json".to_string(),
+            derivation_path: String::new(),
+            key_rotation_interval: 3600,
+            pool_size: 4,
+            cold_address: String::new(),
+            sweep_floor_lamports: 100_000_000,
+            fee_reserve_lamports: 50_000_000,
+            rebalance_interval: 300,
+            rebalance_threshold: 0.25,
+        }
+    }
+}
+
+impl Default for TradingSettings {
+    fn default() -> Self {
+        TradingSettings {
+            markets: MarketSettings::default(),
+            execution: ExecutionSettings::default(),
+            risk: RiskSettings::default(),
+            inventory: InventorySettings::default(),
+        }
+    }
+}
+
+impl Default for InventorySettings {
+    fn default() -> Self {
+        InventorySettings {
+            // Wrapped SOL
+            base_mint: "So11111111111111111111111111111111111111112".to_string(),
+            dust_amount: 1000,
+            max_unwind_slippage: 0.02,
+            scan_interval_ms: 5000,
+            targets: Vec::new(),
+            max_rebalance_slippage: 0.01,
+            rebalance_interval_ms: 60_000,
+            quiet_period_secs: 30,
+        }
+    }
+}
+
+impl Default for MarketSettings {
+    fn default() -> Self {
+        MarketSettings {
+            whitelisted_markets: vec![],
+            whitelisted_tokens: vec![],
+            blacklisted_markets: vec![],
+            min_liquidity: 1000000,
+            max_spread: 0.05,
+            max_state_age_slots: 4,
+            max_snapshot_slot_spread: 0,
+        }
+    }
+}
+
+impl Default for ExecutionSettings {
+    fn default() -> Self {
+        ExecutionSettings {
+            max_concurrent_trades: 3,
+            min_profit_threshold: 0.01,
+            max_position_size: 1000000000,
+            flash_loan_enabled: true,
+            flash_loan_sources: vec!["solend".to_string(), "port".to_string()],
+            execution_strategies: vec!["jit".to_string(), "flash_loan".to_string()],
+            max_opportunity_age_slots: FreshnessSettings::default(),
+            simulate_trades: false,
+            account_lock_timeout_ms: 60_000,
+            tip_accounts: vec![],
+            shutdown_drain_secs: 30,
+        }
+    }
+}
+
+impl Default for FreshnessSettings {
+    fn default() -> Self {
+        FreshnessSettings {
+            direct: 4,
+            triangular: 4,
+            jit: 4,
+            flash_loan: 2,
+            front_running: 1,
+        }
+    }
+}
+
+impl Default for RiskSettings {
+    fn default() -> Self {
+        RiskSettings {
+            max_loss_threshold: 0.02,
+            daily_volume_limit: 1000000000000,
+            position_timeout: 30000,
+            slippage_tolerance: 0.01,
+            state_path: "risk_state.json".to_string(),
+            circuit_breaker: CircuitBreakerSettings::default(),
+        }
+    }
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        CircuitBreakerSettings {
+            max_consecutive_failures: 3,
+            max_single_loss: 50_000_000,
+            max_profit_divergence: 0.5,
+            cooldown_secs: 300,
+            state_path: "circuit_breaker.json".to_string(),
+        }
+    }
+}
+
+impl Default for SecuritySettings {
+    fn default() -> Self {
+        SecuritySettings {
+            level: SecurityLevel::High,
+            mev_protection: MevProtectionSettings::default(),
+            keystore: KeystoreSettings::default(),
+            signer: SignerSettings::default(),
+            transaction_guards: TransactionGuardSettings::default(),
+            admin: AdminSettings::default(),
+        }
+    }
+}
+
+impl Default for AdminSettings {
+    fn default() -> Self {
+        AdminSettings {
+            enabled: false,
+            address: "127.0.0.1:9101".to_string(),
+            token_env: "ADMIN_API_TOKEN".to_string(),
+        }
+    }
+}
+
+impl Default for MevProtectionSettings {
+    fn default() -> Self {
+        MevProtectionSettings {
+            enabled: true,
+            protection_level: 2,
+            sandwich_detection: true,
+            frontrunning_detection: true,
+            backrunning_detection: true,
+        }
+    }
+}
+
+impl Default for KeystoreSettings {
+    fn default() -> Self {
+        KeystoreSettings {
+            enabled: false,
+            path: "keystore.json".to_string(),
+            passphrase_env: "KEYSTORE_PASSPHRASE".to_string(),
+        }
+    }
+}
+
+impl Default for SignerSettings {
+    fn default() -> Self {
+        SignerSettings {
+            backend: "local".to_string(),
+            socket_path: "signer.sock".to_string(),
+            auth_secret_env: "SIGNER_AUTH_SECRET".to_string(),
+            timeout_ms: 2000,
+            allowed_programs: vec![
+                "11111111111111111111111111111111".to_string(),
+                "ComputeBudget111111111111111111111111111111".to_string(),
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+                "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL".to_string(),
+            ],
+            max_transfer_lamports: 1_000_000_000,
+            max_token_amount: 1_000_000_000_000,
+        }
+    }
+}
+
+impl Default for TransactionGuardSettings {
+    fn default() -> Self {
+        TransactionGuardSettings {
+            signature_verification: true,
+            timeout_ms: 5000,
+            max_retries: 3,
+            require_confirmations: 1,
+        }
+    }
+}
+
+impl Default for MonitoringSettings {
+    fn default() -> Self {
+        MonitoringSettings {
+            log_level: "info".to_string(),
+            log_format: "text".to_string(),
+            log_file: String::new(),
+            metrics_enabled: true,
+            alert_endpoints: vec![],
+            performance_tracking: true,
+            journal_path: "journal.db".to_string(),
+            metrics_address: "127.0.0.1:9100".to_string(),
+            health_max_market_lag_slots: 32,
+            alert_dedupe_secs: 300,
+            alert_rate_limit: 10,
+            large_loss_alert_lamports: 100_000_000,
+            metrics_snapshot_path: String::new(),
         }
     }
 }
@@ -219,3 +772,44 @@ pub fn parse_market_pairs(input: &str) -> Result<HashMap<Pubkey, Pubkey>, Arbitr
     
     pairs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings_are_valid() {
+        assert!(Settings::default().validation_errors().is_empty());
+    }
+
+    #[test]
+    fn test_validation_collects_every_error() {
+        let mut settings = Settings::default();
+        settings.trading.execution.min_profit_threshold = 1.5;
+        settings.trading.markets.whitelisted_tokens = vec!["token1".to_string()];
+        settings.trading.execution.execution_strategies = vec!["sandwich".to_string()];
+        settings.trading.execution.flash_loan_sources = vec!["aave".to_string()];
+        settings.trading.execution.max_position_size = settings.trading.risk.daily_volume_limit + 1;
+
+        let errors = settings.validation_errors();
+        assert_eq!(errors.len(), 5, "{:#?}", errors);
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_unknown_strategy_gets_the_strictest_freshness_window() {
+        let freshness = FreshnessSettings {
+            direct: 6,
+            triangular: 5,
+            jit: 4,
+            flash_loan: 2,
+            front_running: 3,
+        };
+
+        assert_eq!(freshness.for_strategy("direct"), 6);
+        assert_eq!(freshness.for_strategy("front_running"), 3);
+        assert_eq!(freshness.for_strategy("factory:jit"), 4);
+        assert_eq!(freshness.for_strategy("factory:sandwich"), 2);
+        assert_eq!(freshness.for_strategy(""), 2);
+    }
+}
@@ -0,0 +1,134 @@
+use {
+    solana_sdk::{message::Message, pubkey::Pubkey},
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    },
+};
+
+// Writable accounts held by in-flight trades; two routes that write the same pool or
+// token account must not land in the same block
+#[derive(Default)]
+pub struct AccountLocks {
+    locks: Mutex<HashMap<Pubkey, AccountLock>>,
+    next_owner: AtomicU64,
+}
+
+struct AccountLock {
+    owner: u64,
+    expires_at: Instant,
+}
+
+// Releases its accounts when dropped, normally once the trade has confirmed
+pub struct AccountLockGuard {
+    table: Arc<AccountLocks>,
+    owner: u64,
+    accounts: Vec<Pubkey>,
+}
+
+impl AccountLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Lock every account or none; a lock past its expiry counts as free
+    pub fn try_lock(self: &Arc<Self>, accounts: &[Pubkey], ttl: Duration) -> Option<AccountLockGuard> {
+        let now = Instant::now();
+        let mut locks = self.locks.lock().unwrap();
+
+        if accounts
+            .iter()
+            .any(|account| matches!(locks.get(account), Some(lock) if lock.expires_at > now))
+        {
+            return None;
+        }
+
+        let owner = self.next_owner.fetch_add(1, Ordering::Relaxed);
+        for account in accounts {
+            locks.insert(
+                *account,
+                AccountLock {
+                    owner,
+                    expires_at: now + ttl,
+                },
+            );
+        }
+
+        Some(AccountLockGuard {
+            table: self.clone(),
+            owner,
+            accounts: accounts.to_vec(),
+        })
+    }
+
+    pub fn locked_count(&self) -> usize {
+        let now = Instant::now();
+        self.locks
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|lock| lock.expires_at > now)
+            .count()
+    }
+}
+
+impl Drop for AccountLockGuard {
+    fn drop(&mut self) {
+        let mut locks = self.table.locks.lock().unwrap();
+
+        // An expired lock may already belong to another trade; only release our own
+        for account in &self.accounts {
+            if matches!(locks.get(account), Some(lock) if lock.owner == self.owner) {
+                locks.remove(account);
+            }
+        }
+    }
+}
+
+pub fn writable_accounts(message: &Message) -> Vec<Pubkey> {
+    message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_writable(*index))
+        .map(|(_, key)| *key)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_routes_are_blocked_until_released() {
+        let locks = Arc::new(AccountLocks::new());
+        let pool = Pubkey::new_unique();
+        let first = [pool, Pubkey::new_unique()];
+        let second = [Pubkey::new_unique(), pool];
+
+        let guard = locks.try_lock(&first, Duration::from_secs(60)).unwrap();
+        assert!(locks.try_lock(&second, Duration::from_secs(60)).is_none());
+        assert!(locks.try_lock(&[Pubkey::new_unique()], Duration::from_secs(60)).is_some());
+
+        drop(guard);
+        assert!(locks.try_lock(&second, Duration::from_secs(60)).is_some());
+    }
+
+    #[test]
+    fn test_expired_lock_is_reclaimed() {
+        let locks = Arc::new(AccountLocks::new());
+        let pool = Pubkey::new_unique();
+
+        let stale = locks.try_lock(&[pool], Duration::ZERO).unwrap();
+        let fresh = locks.try_lock(&[pool], Duration::from_secs(60)).unwrap();
+
+        // Dropping the stale guard must not release the new owner's lock
+        drop(stale);
+        assert!(locks.try_lock(&[pool], Duration::from_secs(60)).is_none());
+        drop(fresh);
+    }
+}
@@ -1,95 +1,653 @@
 use {
     crate::{
-        config::Settings,
+        admin::{AdminCommand, AdminReceiver, EngineStatus, StrategyStatus, ThresholdUpdate},
+        config::{FreshnessSettings, Settings, SettingsHandle},
+        dex::venue_name,
+        monitoring::{Alert, AlertSender, AlertSeverity, Metrics},
+        journal::{
+            route_shape, Decision, ExecutionRecord, ExecutionStatus, Journal, Latency, OpportunityRecord,
+        },
+        signer::SharedSigner,
+        risk::{strategy_scope, CircuitBreaker, RiskReservation, TradeOutcome},
+        strategies::RiskManager,
+        wallet::{ExecutionPool, WalletLease},
+        core::{
+            profit_calculator::ProfitCalculator, transaction_builder::TransactionBuilder,
+            writable_accounts, AccountLocks, BalanceChanges, MarketDecoder, MarketSnapshot, MarketStore,
+            ArbitrageStrategy, ModelErrorStats, ModelErrorTracker, RealizedPnl,
+        },
         types::common::{
             ArbitrageError, ArbitrageOpportunity, ExecutionResult,
             FlashLoanParams, MarketState, TokenPair, TradeStep,
         },
     },
-    solana_client::rpc_client::RpcClient,
+    solana_client::{rpc_client::RpcClient, rpc_config::RpcTransactionConfig},
     solana_sdk::{
+        account::Account,
         commitment_config::CommitmentConfig,
         pubkey::Pubkey,
-        signature::Keypair,
+        compute_budget,
+        signature::Signature,
+        system_instruction::SystemInstruction,
+        system_program,
         transaction::Transaction,
     },
+    solana_transaction_status::UiTransactionEncoding,
     std::{
-        sync::Arc,
-        time::{Duration, SystemTime, UNIX_EPOCH},
+        collections::{BTreeMap, HashSet},
+        fmt,
+        path::Path,
+        str::FromStr,
+        sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
     },
-    tokio::sync::RwLock,
+    tokio::{sync::watch, task::JoinHandle},
+    tracing::{field, info_span, Instrument, Span},
 };
 
+// Routes the engine scans for itself, next to the configured factory strategies
+const ENGINE_STRATEGIES: &[&str] = &["direct", "triangular", "flash_loan"];
+// How many of the last cycle's opportunities are kept for the admin API
+const TOP_OPPORTUNITIES: usize = 100;
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+// Compute budget program instruction tags and limits
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u64 = 200_000;
+const MAX_COMPUTE_UNITS: u64 = 1_400_000;
+
 pub struct ArbitrageEngine {
-    settings: Arc<Settings>,
+    settings: SettingsHandle,
     rpc_client: Arc<RpcClient>,
-    market_states: Arc<RwLock<Vec<MarketState>>>,
-    keypair: Arc<Keypair>,
+    market_store: Arc<MarketStore>,
+    wallets: Arc<ExecutionPool>,
+    account_locks: Arc<AccountLocks>,
+    risk: Arc<dyn RiskManager>,
+    breaker: Arc<CircuitBreaker>,
+    journal: Arc<Journal>,
+    metrics: Arc<Metrics>,
+    alerts: AlertSender,
+    model_error: ModelErrorTracker,
+    // Whitelisted markets that failed to load, so each is only reported once
+    unloadable_markets: Mutex<HashSet<Pubkey>>,
+    in_flight: Arc<AtomicUsize>,
+    // Ties every log line and span of one opportunity together
+    next_opportunity_id: AtomicU64,
+    // Set by an emergency stop; checked by in-flight trades before each send
+    stop: watch::Sender<bool>,
+    // Set once on shutdown; no new trades start, in-flight ones run to completion
+    draining: watch::Sender<bool>,
+}
+
+struct StrategyControl {
+    source: &'static str,
+    description: String,
+    paused: bool,
+    found: u64,
+    found_last_cycle: u64,
+}
+
+// Admin-controlled state, owned by the engine loop and changed only through `AdminCommand`s
+struct EngineControls {
+    // Factory strategies are keyed `factory:<name>` so they never shadow an engine route of the same name
+    strategies: BTreeMap<String, StrategyControl>,
+    // Strategies from `StrategyFactory` by control key; the engine scans them and trades what they find
+    factory: Vec<(String, Box<dyn ArbitrageStrategy>)>,
+    paused_markets: HashSet<Pubkey>,
+    top: Vec<ArbitrageOpportunity>,
+}
+
+impl EngineControls {
+    fn new(factory: Vec<(String, Box<dyn ArbitrageStrategy>)>) -> Self {
+        let mut strategies: BTreeMap<String, StrategyControl> = ENGINE_STRATEGIES
+            .iter()
+            .map(|name| {
+                let control = StrategyControl {
+                    source: "engine",
+                    description: format!("{} routes found by the engine", name),
+                    paused: false,
+                    found: 0,
+                    found_last_cycle: 0,
+                };
+                (name.to_string(), control)
+            })
+            .collect();
+
+        let mut strategy_objects = Vec::new();
+        for (name, strategy) in factory {
+            let control = StrategyControl {
+                source: "factory",
+                description: format!("{} run by the engine", strategy.name()),
+                paused: false,
+                found: 0,
+                found_last_cycle: 0,
+            };
+            let key = format!("factory:{}", name);
+            strategies.insert(key.clone(), control);
+            strategy_objects.push((key, strategy));
+        }
+
+        Self {
+            strategies,
+            factory: strategy_objects,
+            paused_markets: HashSet::new(),
+            top: Vec::new(),
+        }
+    }
+
+    fn record_found(&mut self, opportunities: &[ArbitrageOpportunity]) {
+        for control in self.strategies.values_mut() {
+            control.found_last_cycle = 0;
+        }
+        for opportunity in opportunities {
+            if let Some(control) = self.strategies.get_mut(&opportunity.strategy) {
+                control.found += 1;
+                control.found_last_cycle += 1;
+            }
+        }
+
+        self.top = opportunities.to_vec();
+        self.top.sort_by(|a, b| b.estimated_profit.cmp(&a.estimated_profit));
+        self.top.truncate(TOP_OPPORTUNITIES);
+    }
+
+    fn paused(&self, opportunity: &ArbitrageOpportunity, markets: &[Pubkey]) -> Option<String> {
+        if self.strategies.get(&opportunity.strategy).map_or(false, |control| control.paused) {
+            return Some(format!("Strategy {} paused by admin", opportunity.strategy));
+        }
+        markets
+            .iter()
+            .find(|market| self.paused_markets.contains(market))
+            .map(|market| format!("Market {} paused by admin", market))
+    }
+}
+
+// Outcome of `ArbitrageEngine::dry_run`; nothing is sent or journaled
+#[derive(Debug, Clone)]
+pub struct DryRun {
+    pub wallet: Pubkey,
+    // Whether the route still clears `min_profit_threshold` against freshly loaded markets
+    pub profitable_now: bool,
+    pub simulation_error: Option<String>,
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+}
+
+// How far a failed trade got; failures from the send onwards may have reached the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TradeStage {
+    // Building, locking or simulating; nothing was sent
+    Prepare,
+    Send,
+    Confirm,
+}
+
+#[derive(Debug)]
+struct TradeError {
+    stage: TradeStage,
+    error: ArbitrageError,
+}
+
+impl TradeError {
+    fn reached_network(&self) -> bool {
+        self.stage != TradeStage::Prepare
+    }
+}
+
+// Errors raised with `?` happen before anything is sent
+impl From<ArbitrageError> for TradeError {
+    fn from(error: ArbitrageError) -> Self {
+        TradeError {
+            stage: TradeStage::Prepare,
+            error,
+        }
+    }
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} failed: {}", self.stage, self.error)
+    }
+}
+
+// Why validation dropped an opportunity; `label` is the fixed metric reason for it
+#[derive(Debug)]
+struct Rejection {
+    label: &'static str,
+    reason: String,
+}
+
+impl Rejection {
+    fn new(label: &'static str, reason: String) -> Self {
+        Rejection { label, reason }
+    }
+}
+
+// Counts a spawned trade against `max_concurrent_trades` until it finishes
+struct TradeSlot(Arc<AtomicUsize>);
+
+impl TradeSlot {
+    fn acquire(in_flight: &Arc<AtomicUsize>) -> Self {
+        in_flight.fetch_add(1, Ordering::SeqCst);
+        TradeSlot(in_flight.clone())
+    }
+}
+
+impl Drop for TradeSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Waits for trade slots to be released, giving up at `deadline`; returns how many are still held
+async fn drain_in_flight(in_flight: &AtomicUsize, deadline: Duration) -> usize {
+    let started = Instant::now();
+    loop {
+        let remaining = in_flight.load(Ordering::SeqCst);
+        if remaining == 0 || started.elapsed() >= deadline {
+            return remaining;
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
 }
 
 impl ArbitrageEngine {
     pub fn new(
-        settings: Settings,
-        keypair: Keypair,
+        settings: SettingsHandle,
+        wallets: Arc<ExecutionPool>,
+        risk: Arc<dyn RiskManager>,
+        breaker: Arc<CircuitBreaker>,
+        journal: Arc<Journal>,
+        metrics: Arc<Metrics>,
+        alerts: AlertSender,
     ) -> Result<Self, ArbitrageError> {
-        let rpc_client = RpcClient::new_with_commitment(
-            settings.network.rpc_endpoints[0].clone(),
+        let startup_settings = settings.load();
+        let rpc_client = Arc::new(RpcClient::new_with_commitment(
+            startup_settings.network.rpc_endpoints[0].clone(),
             CommitmentConfig::confirmed(),
+        ));
+
+        let market_store = MarketStore::new(
+            rpc_client.clone(),
+            Arc::new(EngineMarketDecoder),
+            startup_settings.trading.markets.max_snapshot_slot_spread,
         );
 
         Ok(Self {
-            settings: Arc::new(settings),
-            rpc_client: Arc::new(rpc_client),
-            market_states: Arc::new(RwLock::new(Vec::new())),
-            keypair: Arc::new(keypair),
+            settings,
+            rpc_client,
+            market_store: Arc::new(market_store),
+            wallets,
+            account_locks: Arc::new(AccountLocks::new()),
+            risk,
+            breaker,
+            journal,
+            metrics,
+            alerts,
+            model_error: ModelErrorTracker::new(),
+            unloadable_markets: Mutex::new(HashSet::new()),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            next_opportunity_id: AtomicU64::new(1),
+            stop: watch::channel(false).0,
+            draining: watch::channel(false).0,
         })
     }
 
-    pub async fn start(&self) -> Result<(), ArbitrageError> {
+    // Follows emergency stops and resumes, e.g. so the rebalancer stands down too
+    pub fn stop_signal(&self) -> watch::Receiver<bool> {
+        self.stop.subscribe()
+    }
+
+    fn stopped(&self) -> bool {
+        *self.stop.borrow()
+    }
+
+    // Set once `shutdown` is called, so background tasks stop alongside the engine
+    pub fn shutdown_signal(&self) -> watch::Receiver<bool> {
+        self.draining.subscribe()
+    }
+
+    // Makes `start` stop taking opportunities, wait for in-flight trades and return
+    pub fn shutdown(&self) {
+        self.draining.send_replace(true);
+    }
+
+    fn draining(&self) -> bool {
+        *self.draining.borrow()
+    }
+
+    pub async fn start(
+        self: Arc<Self>,
+        strategies: Vec<(String, Box<dyn ArbitrageStrategy>)>,
+        mut commands: AdminReceiver,
+    ) -> Result<(), ArbitrageError> {
         log::info!("Starting arbitrage engine...");
+        let mut controls = EngineControls::new(strategies);
         
         // Initialize market monitoring
         self.init_market_monitoring().await?;
         
         // Main arbitrage loop
-        loop {
-            if let Err(e) = self.arbitrage_cycle().await {
+        let mut draining = self.draining.subscribe();
+        while !self.draining() {
+            // Apply admin commands between cycles
+            while let Ok(command) = commands.try_recv() {
+                self.handle_command(&mut controls, command);
+            }
+
+            // After an emergency stop, do nothing until told otherwise or shut down
+            if self.stopped() {
+                tokio::select! {
+                    command = commands.recv() => match command {
+                        Some(command) => self.handle_command(&mut controls, command),
+                        None => tokio::time::sleep(Duration::from_secs(1)).await,
+                    },
+                    _ = draining.changed() => {}
+                }
+                continue;
+            }
+
+            if let Err(e) = self.arbitrage_cycle(&mut controls, &mut commands).await {
                 log::error!("Error in arbitrage cycle: {}", e);
                 tokio::time::sleep(Duration::from_secs(1)).await;
             }
         }
+
+        self.finish_shutdown().await;
+        Ok(())
+    }
+
+    async fn finish_shutdown(&self) {
+        let settings = self.settings.load();
+        let deadline = Duration::from_secs(settings.trading.execution.shutdown_drain_secs);
+        log::info!(
+            "Shutting down, waiting up to {}s for {} in-flight trades",
+            deadline.as_secs(),
+            self.in_flight.load(Ordering::SeqCst)
+        );
+
+        let remaining = drain_in_flight(&self.in_flight, deadline).await;
+        if remaining > 0 {
+            log::warn!(
+                "{} trades still in flight after {}s; exiting without their outcome",
+                remaining,
+                deadline.as_secs()
+            );
+        } else {
+            log::info!("All in-flight trades finished");
+        }
+
+        if let Err(e) = self.journal.checkpoint() {
+            log::error!("{}", e);
+        }
+        let snapshot = &settings.monitoring.metrics_snapshot_path;
+        if settings.monitoring.metrics_enabled && !snapshot.is_empty() {
+            if let Err(e) = self.metrics.write_snapshot(Path::new(snapshot)) {
+                log::error!("{}", e);
+            }
+        }
+    }
+
+    // A dropped reply only means the requester stopped waiting
+    fn handle_command(&self, controls: &mut EngineControls, command: AdminCommand) {
+        match command {
+            AdminCommand::Status(reply) => {
+                let _ = reply.send(self.status(controls));
+            }
+            AdminCommand::PauseStrategy { strategy, paused, reply } => {
+                let result = match controls.strategies.get_mut(&strategy) {
+                    Some(control) => {
+                        control.paused = paused;
+                        Ok(())
+                    }
+                    None => Err(ArbitrageError::ConfigError(format!(
+                        "Unknown strategy {} (expected one of {})",
+                        strategy,
+                        controls.strategies.keys().cloned().collect::<Vec<_>>().join(", ")
+                    ))),
+                };
+                let _ = reply.send(result);
+            }
+            AdminCommand::PauseMarket { market, paused, reply } => {
+                if paused {
+                    controls.paused_markets.insert(market);
+                } else {
+                    controls.paused_markets.remove(&market);
+                }
+                let _ = reply.send(());
+            }
+            AdminCommand::UpdateThresholds { update, reply } => {
+                let _ = reply.send(self.update_thresholds(update));
+            }
+            AdminCommand::EmergencyStop(reply) => {
+                self.stop.send_replace(true);
+                self.alerts.send(Alert::new(
+                    AlertSeverity::Critical,
+                    "admin",
+                    format!("Emergency stop with {} trades in flight", self.in_flight.load(Ordering::SeqCst)),
+                ));
+                let _ = reply.send(());
+            }
+            AdminCommand::Resume(reply) => {
+                self.stop.send_replace(false);
+                self.alerts.send(Alert::new(AlertSeverity::Warning, "admin", "Trading resumed after emergency stop"));
+                let _ = reply.send(());
+            }
+            AdminCommand::TopOpportunities { limit, reply } => {
+                let _ = reply.send(controls.top.iter().take(limit).cloned().collect());
+            }
+        }
+    }
+
+    fn status(&self, controls: &EngineControls) -> EngineStatus {
+        let settings = self.settings.load();
+        EngineStatus {
+            stopped: self.stopped(),
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            strategies: controls
+                .strategies
+                .iter()
+                .map(|(name, control)| StrategyStatus {
+                    name: name.clone(),
+                    source: control.source,
+                    description: control.description.clone(),
+                    paused: control.paused,
+                    found: control.found,
+                    found_last_cycle: control.found_last_cycle,
+                })
+                .collect(),
+            paused_markets: controls.paused_markets.iter().map(|market| market.to_string()).collect(),
+            min_profit_threshold: settings.trading.execution.min_profit_threshold,
+            max_position_size: settings.trading.execution.max_position_size,
+            max_concurrent_trades: settings.trading.execution.max_concurrent_trades,
+            slippage_tolerance: settings.trading.risk.slippage_tolerance,
+        }
+    }
+
+    // Goes through the same validation as a config reload; returns the changes made
+    // Applied as overrides so a later settings file reload does not silently revert them
+    fn update_thresholds(&self, update: ThresholdUpdate) -> Result<Vec<String>, ArbitrageError> {
+        let mut overrides = Vec::new();
+        if let Some(threshold) = update.min_profit_threshold {
+            overrides.push(format!("trading.execution.min_profit_threshold={}", threshold));
+        }
+        if let Some(size) = update.max_position_size {
+            overrides.push(format!("trading.execution.max_position_size={}", size));
+        }
+        if let Some(trades) = update.max_concurrent_trades {
+            overrides.push(format!("trading.execution.max_concurrent_trades={}", trades));
+        }
+        if let Some(slippage) = update.slippage_tolerance {
+            overrides.push(format!("trading.risk.slippage_tolerance={}", slippage));
+        }
+
+        let changes = self.settings.apply_overrides(&overrides)?;
+        Ok(changes.iter().map(|change| change.to_string()).collect())
     }
 
-    async fn arbitrage_cycle(&self) -> Result<(), ArbitrageError> {
+    async fn arbitrage_cycle(
+        self: &Arc<Self>,
+        controls: &mut EngineControls,
+        commands: &mut AdminReceiver,
+    ) -> Result<(), ArbitrageError> {
+        // Pick up markets added to the whitelist since the last cycle
+        self.sync_watched_markets().await?;
+        
         // Find arbitrage opportunities
-        let opportunities = self.find_opportunities().await?;
+        let opportunities = self
+            .find_opportunities(&mut controls.factory)
+            .instrument(info_span!("detect"))
+            .await?;
+        let found_at = Instant::now();
+        controls.record_found(&opportunities);
         
         for opportunity in opportunities {
-            // Validate opportunity
-            if !self.validate_opportunity(&opportunity).await? {
+            // Apply admin commands between opportunities too, so a pause or stop lands mid-cycle
+            while let Ok(command) = commands.try_recv() {
+                self.handle_command(controls, command);
+            }
+            if self.stopped() {
+                break;
+            }
+
+            self.metrics.opportunity_found(&opportunity.strategy);
+            let span = info_span!(
+                "opportunity",
+                id = self.next_opportunity_id.fetch_add(1, Ordering::Relaxed),
+                strategy = %opportunity.strategy,
+                market = %opportunity.source_market,
+                slot = opportunity.slot,
+                estimated_profit = opportunity.estimated_profit,
+                journal_id = field::Empty,
+            );
+
+            // Skip strategies and markets paused through the admin API
+            if let Some(reason) = controls.paused(&opportunity, &self.route_markets(&opportunity)) {
+                self.rejected(&opportunity, Decision::Skipped, reason, "admin_paused");
+                continue;
+            }
+
+            // Leave the rest for the next cycle once every trade slot is taken
+            let max_concurrent = self.settings.load().trading.execution.max_concurrent_trades as usize;
+            if self.in_flight.load(Ordering::SeqCst) >= max_concurrent {
+                log::debug!(
+                    "{} trades in flight, deferring opportunity on {}",
+                    max_concurrent,
+                    opportunity.source_market
+                );
+                let reason = "All trade slots busy".to_string();
+                self.rejected(&opportunity, Decision::Skipped, reason, "slots_busy");
+                continue;
+            }
+
+            // Skip strategies and markets paused by the circuit breaker
+            if let Some(reason) = self.breaker.blocked(&opportunity) {
+                log::debug!("Skipping opportunity on {}: {}", opportunity.source_market, reason);
+                self.rejected(&opportunity, Decision::Skipped, reason, "circuit_breaker");
                 continue;
             }
+
+            // Validate opportunity; an error only rules out this opportunity, not the rest of the cycle
+            match self
+                .validate_opportunity(&opportunity)
+                .instrument(info_span!(parent: &span, "quote"))
+                .await
+            {
+                Ok(None) => {}
+                Ok(Some(rejection)) => {
+                    self.rejected(&opportunity, Decision::Skipped, rejection.reason, rejection.label);
+                    continue;
+                }
+                Err(e) => {
+                    self.rejected(&opportunity, Decision::Skipped, format!("Validation failed: {}", e), "validation");
+                    continue;
+                }
+            }
             
             // Check profitability
-            if !self.is_profitable(&opportunity).await? {
-                continue;
+            match self
+                .is_profitable(&opportunity)
+                .instrument(info_span!(parent: &span, "profitability"))
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.rejected(&opportunity, Decision::Skipped, "Not profitable".to_string(), "not_profitable");
+                    continue;
+                }
+                Err(e) => {
+                    let reason = format!("Profitability check failed: {}", e);
+                    self.rejected(&opportunity, Decision::Skipped, reason, "not_profitable");
+                    continue;
+                }
             }
+
+            // Stay within the rolling daily volume and loss budget; the reservation is released if the trade
+            // never reaches the network
+            let reservation = match RiskReservation::acquire(&self.risk, &opportunity)? {
+                Some(reservation) => reservation,
+                None => {
+                    self.rejected(&opportunity, Decision::Skipped, "Risk limit".to_string(), "risk_limit");
+                    continue;
+                }
+            };
             
-            // Execute the arbitrage
-            match self.execute_arbitrage(&opportunity).await {
-                Ok(result) => {
-                    if result.success {
-                        log::info!(
-                            "Successfully executed arbitrage. Profit: {} SOL, Signature: {}",
-                            result.profit_realized.unwrap_or(0) as f64 / 1e9,
-                            result.transaction_signature.unwrap_or_default()
-                        );
+            // A shutdown lets running trades finish but starts no new ones
+            if self.draining() {
+                break;
+            }
+
+            // Execute the arbitrage alongside other in-flight trades
+            let slot = TradeSlot::acquire(&self.in_flight);
+            let engine = self.clone();
+            tokio::spawn(async move {
+                let _slot = slot;
+                let result = engine.execute_arbitrage(&opportunity, reservation, found_at).await;
+
+                // Only trades that reached the network count towards the breaker; skipped ones cost nothing
+                let outcome = match &result {
+                    // Without a measured outcome there is nothing to compare the simulation against
+                    Ok(result) if result.success => result.profit_realized.map(|realized_profit| {
+                        TradeOutcome::Landed {
+                            simulated_profit: opportunity.estimated_profit as i64,
+                            realized_profit,
+                        }
+                    }),
+                    Ok(result) if result.transaction_signature.is_some() => Some(TradeOutcome::Failed),
+                    Ok(_) => None,
+                    Err(e) if e.reached_network() => Some(TradeOutcome::Failed),
+                    // Build and simulation errors say nothing about how the route trades
+                    Err(_) => None,
+                };
+                if let Some(outcome) = outcome {
+                    if let Err(e) = engine.breaker.record(&opportunity, outcome) {
+                        log::error!("Failed to record trade with the circuit breaker: {}", e);
                     }
                 }
-                Err(e) => {
-                    log::error!("Failed to execute arbitrage: {}", e);
+
+                match result {
+                    Ok(result) => {
+                        if result.success {
+                            log::info!(
+                                "Successfully executed arbitrage. Profit: {} SOL (estimated {}), Signature: {}",
+                                result.profit_realized.unwrap_or(0) as f64 / 1e9,
+                                opportunity.estimated_profit as f64 / 1e9,
+                                result.transaction_signature.unwrap_or_default()
+                            );
+                        } else if let Some(error) = result.error {
+                            log::debug!("Skipped arbitrage on {}: {}", opportunity.source_market, error);
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Failed to execute arbitrage: {}", e);
+                    }
                 }
             }
+            .instrument(span));
         }
         
         Ok(())
@@ -98,16 +656,69 @@ impl ArbitrageEngine {
     async fn init_market_monitoring(&self) -> Result<(), ArbitrageError> {
         let markets = self.get_whitelisted_markets().await?;
         
-        for market in markets {
-            self.add_market_state(market).await?;
+        // Load every market in a single read so the initial view is slot-consistent
+        if !markets.is_empty() {
+            let failures = self.market_store.load(&markets).await?;
+            self.report_unloadable(&markets, failures);
+        }
+        
+        Ok(())
+    }
+
+    async fn sync_watched_markets(&self) -> Result<(), ArbitrageError> {
+        let mut missing = Vec::new();
+        for market in self.get_whitelisted_markets().await? {
+            if self.market_store.get(&market).await.is_none() {
+                missing.push(market);
+            }
+        }
+        
+        if !missing.is_empty() {
+            log::debug!("Loading {} unwatched whitelisted markets", missing.len());
+            let failures = self.market_store.load(&missing).await?;
+            self.report_unloadable(&missing, failures);
         }
         
         Ok(())
     }
 
-    async fn find_opportunities(&self) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
+    // A market that cannot be loaded, e.g. a typo in a reloaded whitelist, is skipped and retried each cycle
+    // while the rest keep trading; it is reported when it first fails and when it finally loads
+    fn report_unloadable(&self, attempted: &[Pubkey], failures: Vec<(Pubkey, ArbitrageError)>) {
+        let mut unloadable = self.unloadable_markets.lock().unwrap();
+        for market in attempted {
+            if !failures.iter().any(|(failed, _)| failed == market) && unloadable.remove(market) {
+                log::info!("Whitelisted market {} loaded", market);
+            }
+        }
+
+        for (market, error) in failures {
+            if !unloadable.insert(market) {
+                log::debug!("Whitelisted market {} still cannot be loaded: {}", market, error);
+                continue;
+            }
+            let message = format!("Skipping whitelisted market {}: {}", market, error);
+            log::warn!("{}", message);
+            self.alerts.send(Alert::new(AlertSeverity::Warning, "market", message).with_key(market.to_string()));
+        }
+    }
+
+    async fn find_opportunities(
+        &self,
+        factory: &mut [(String, Box<dyn ArbitrageStrategy>)],
+    ) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
         let mut opportunities = Vec::new();
-        let market_states = self.market_states.read().await;
+        let settings = self.settings.load();
+        let whitelisted = self.get_whitelisted_markets().await?;
+        
+        // Only consider markets that are currently whitelisted and not blacklisted
+        let market_states: Vec<MarketState> = self
+            .market_store
+            .all()
+            .await
+            .into_iter()
+            .filter(|state| whitelisted.contains(&state.market_address))
+            .collect();
         
         // Find direct arbitrage opportunities
         opportunities.extend(self.find_direct_arbitrage(&market_states)?);
@@ -116,9 +727,12 @@ impl ArbitrageEngine {
         opportunities.extend(self.find_triangular_arbitrage(&market_states)?);
         
         // Find flash loan opportunities if enabled
-        if self.settings.trading.execution.flash_loan_enabled {
+        if settings.trading.execution.flash_loan_enabled {
             opportunities.extend(self.find_flash_loan_arbitrage(&market_states)?);
         }
+
+        // Then the configured `execution_strategies`, against the same markets
+        opportunities.extend(scan_factory(factory, settings, market_states, &whitelisted));
         
         Ok(opportunities)
     }
@@ -183,102 +797,515 @@ impl ArbitrageEngine {
         Ok(opportunities)
     }
 
-    async fn validate_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
+    async fn validate_opportunity(
+        self: &Arc<Self>,
+        opportunity: &ArbitrageOpportunity,
+    ) -> Result<Option<Rejection>, ArbitrageError> {
         // Check if the opportunity is still valid
-        if SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
-            - opportunity.timestamp
-            > 5
-        {
-            return Ok(false);
+        let current_slot = self.blocking(Span::current(), |engine| engine.get_current_slot()).await?;
+        let settings = self.settings.load();
+        let freshness = &settings.trading.execution.max_opportunity_age_slots;
+        if let Some(rejection) = stale_opportunity(opportunity, current_slot, freshness) {
+            return Ok(Some(rejection));
         }
         
-        // Validate market states
-        let market_states = self.market_states.read().await;
-        for step in &opportunity.route {
-            if !self.validate_market_state(&market_states, &step.market)? {
-                return Ok(false);
+        // Validate market states against a slot-consistent view of the route; cached states are good enough as long
+        // as they are no older than the opportunity and still within the freshness limit
+        let markets = self.route_markets(opportunity);
+        let freshness_floor = current_slot.saturating_sub(settings.trading.markets.max_state_age_slots);
+        let min_slot = opportunity.slot.max(freshness_floor);
+        let snapshot = self.market_store.snapshot(&markets, min_slot).await?;
+        for market in &markets {
+            if let Some(rejection) = self.validate_market_state(&snapshot, market, current_slot) {
+                return Ok(Some(rejection));
             }
         }
         
+        // Re-quote the route against the latest state
+        if let Some(rejection) = self.requote_opportunity(opportunity, &snapshot)? {
+            return Ok(Some(rejection));
+        }
+        
         // Check security constraints
         if !self.check_security_constraints(opportunity)? {
-            return Ok(false);
+            return Ok(Some(Rejection::new("security", "Failed security constraints".to_string())));
         }
         
-        Ok(true)
+        Ok(None)
     }
 
-    async fn execute_arbitrage(&self, opportunity: &ArbitrageOpportunity) -> Result<ExecutionResult, ArbitrageError> {
-        let start_time = SystemTime::now();
-        
-        // Build transaction
-        let transaction = self.build_arbitrage_transaction(opportunity)?;
-        
-        // Simulate transaction
-        if !self.simulate_transaction(&transaction)? {
-            return Ok(ExecutionResult {
+    async fn execute_arbitrage(
+        self: &Arc<Self>,
+        opportunity: &ArbitrageOpportunity,
+        reservation: RiskReservation,
+        found_at: Instant,
+    ) -> Result<ExecutionResult, TradeError> {
+        // Each in-flight trade gets its own wallet and token accounts
+        let lease = match self.wallets.lease() {
+            Some(lease) => lease,
+            None => return Ok(self.skipped(opportunity, Decision::Skipped, "No idle execution wallet", "no_wallet")),
+        };
+
+        let result = self.execute_with_wallet(opportunity, &lease, reservation, found_at).await;
+        let recorded = match &result {
+            Ok(result) => result.clone(),
+            Err(e) => ExecutionResult {
                 success: false,
                 profit_realized: None,
-                error: Some("Transaction simulation failed".to_string()),
+                error: Some(e.error.to_string()),
                 transaction_signature: None,
                 execution_time_ms: 0,
-            });
-        }
+            },
+        };
+        self.wallets.record_result(&lease.pubkey(), &recorded);
+
+        result
+    }
+
+    async fn execute_with_wallet(
+        self: &Arc<Self>,
+        opportunity: &ArbitrageOpportunity,
+        lease: &WalletLease,
+        reservation: RiskReservation,
+        found_at: Instant,
+    ) -> Result<ExecutionResult, TradeError> {
+        let start_time = Instant::now();
+        let mut latency = Latency::default();
         
+        // Build transaction
+        let transaction = info_span!("build")
+            .in_scope(|| self.build_arbitrage_transaction(opportunity, lease.signer()))
+            .map(Arc::new)
+            .map_err(|e| {
+                self.rejected(opportunity, Decision::Skipped, e.to_string(), "build_failed");
+                e
+            })?;
+        latency.build_ms = Latency::millis(start_time.elapsed());
+
+        // Hold every writable account until confirmation so overlapping routes wait their turn
+        let lock_timeout = Duration::from_millis(self.settings.load().trading.execution.account_lock_timeout_ms);
+        let _account_locks = match self
+            .account_locks
+            .try_lock(&writable_accounts(&transaction.message), lock_timeout)
+        {
+            Some(guard) => guard,
+            None => {
+                return Ok(self.skipped(
+                    opportunity,
+                    Decision::Skipped,
+                    "Route writes accounts held by an in-flight trade",
+                    "accounts_locked",
+                ));
+            }
+        };
+        
+        // An emergency stop cancels every trade that has not been sent yet
+        if self.stopped() {
+            return Ok(self.skipped(opportunity, Decision::Skipped, "Emergency stop", "emergency_stop"));
+        }
+
+        // Simulate transaction
+        let simulate_start = Instant::now();
+        let simulated = {
+            let transaction = transaction.clone();
+            self.blocking(info_span!("simulate"), move |engine| engine.simulate_transaction(&transaction))
+        }
+        .await
+        .map_err(|e| {
+            self.rejected(opportunity, Decision::Simulated, e.to_string(), "simulation_error");
+            e
+        })?;
+        latency.simulate_ms = Latency::millis(simulate_start.elapsed());
+        if !simulated {
+            return Ok(self.skipped(
+                opportunity,
+                Decision::Simulated,
+                "Transaction simulation failed",
+                "simulation_failed",
+            ));
+        }
+
+        // Last chance to honour an emergency stop raised during simulation; checked before anything is journaled
+        // as sent so the opportunity is recorded only once
+        if self.stopped() {
+            return Ok(self.skipped(opportunity, Decision::Skipped, "Emergency stop", "emergency_stop"));
+        }
+
+        // Written while the transaction goes out; the id is only needed once it has been sent
+        let decision = self.journal_decision(OpportunityRecord::new(opportunity, Decision::Sent, None));
+
         // Send transaction
-        let signature = self.send_transaction(&transaction)?;
+        self.metrics.observe_quote_to_send(found_at.elapsed());
+        self.metrics.trade_sent(&opportunity.strategy);
+        let send_start = Instant::now();
+        let sent = {
+            let transaction = transaction.clone();
+            let span = info_span!("send", wallet = %lease.pubkey());
+            self.blocking(span, move |engine| engine.send_transaction(&transaction)).await
+        };
+        latency.send_ms = Latency::millis(send_start.elapsed());
+        let opportunity_id = decision.await.ok().flatten();
+        if let Some(id) = opportunity_id {
+            Span::current().record("journal_id", id);
+        }
+        let mut record = execution_record(opportunity, opportunity_id, &transaction, &lease.pubkey());
+        let signature = match sent {
+            Ok(signature) => signature,
+            Err(e) => {
+                self.metrics.trade_failed(&opportunity.strategy);
+                latency.total_ms = Latency::millis(start_time.elapsed());
+                self.journal_execution(record, latency, Err(&e));
+                return Err(TradeError {
+                    stage: TradeStage::Send,
+                    error: e,
+                });
+            }
+        };
+        record.signature = Some(signature.clone());
         
         // Wait for confirmation
-        self.confirm_transaction(&signature)?;
-        
-        let execution_time = SystemTime::now()
-            .duration_since(start_time)
-            .unwrap()
-            .as_millis() as u64;
+        let confirm_start = Instant::now();
+        let confirmed = {
+            let signature = signature.clone();
+            let span = info_span!("confirm", signature = %signature);
+            self.blocking(span, move |engine| engine.confirm_transaction(&signature)).await
+        };
+        latency.confirm_ms = Latency::millis(confirm_start.elapsed());
+        latency.total_ms = Latency::millis(start_time.elapsed());
+        if let Err(e) = confirmed {
+            // The transaction was sent, so its fee counts as a loss even though the trade did not land
+            self.settle_risk(reservation, -(transaction_fee(&transaction) as i64));
+            self.metrics.trade_failed(&opportunity.strategy);
+            self.journal_execution(record, latency, Err(&e));
+            return Err(TradeError {
+                stage: TradeStage::Confirm,
+                error: e,
+            });
+        }
+
+        // The trade has landed either way; a failed measurement only leaves its profit unknown
+        let measured = {
+            let (transaction, wallet, signature) = (transaction.clone(), lease.pubkey(), signature.clone());
+            self.blocking(info_span!("measure"), move |engine| {
+                engine.measure_realized_pnl(&transaction, &wallet, &signature)
+            })
+            .await
+        };
+        let profit_realized = match measured {
+            Ok((slot, pnl)) => {
+                let mut scopes = vec![strategy_scope(&opportunity.strategy)];
+                scopes.extend(record.venues.iter().map(|venue| format!("dex:{}", venue)));
+                self.model_error.record(&scopes, record.estimated_profit, pnl.net);
+
+                let alert_threshold = self.settings.load().monitoring.large_loss_alert_lamports;
+                if pnl.net < 0 && pnl.net.unsigned_abs() >= alert_threshold {
+                    self.alerts.send(Alert::new(
+                        AlertSeverity::Critical,
+                        "large_loss",
+                        format!(
+                            "{} trade {} lost {:.4} SOL (estimated {:+.4} SOL)",
+                            opportunity.strategy,
+                            signature,
+                            pnl.net.unsigned_abs() as f64 / 1e9,
+                            record.estimated_profit as f64 / 1e9
+                        ),
+                    ));
+                }
+
+                record.slot = Some(slot);
+                record.realized_profit = Some(pnl.net);
+                record.gross_profit = Some(pnl.gross);
+                record.fee = pnl.fee;
+                record.tip = pnl.tip;
+                record.rent = pnl.rent;
+                Some(pnl.net)
+            }
+            Err(e) => {
+                log::warn!("Could not measure realized PnL of {}: {}", signature, e);
+                None
+            }
+        };
+
+        // An unmeasured trade is budgeted as losing what it certainly paid: its fee and tips
+        let budgeted = profit_realized.unwrap_or_else(|| {
+            let tips = transaction_tips(&transaction, &self.tip_accounts());
+            -((transaction_fee(&transaction) + tips) as i64)
+        });
+        self.settle_risk(reservation, budgeted);
+        self.metrics.trade_landed(&opportunity.strategy, profit_realized);
+        self.journal_execution(record, latency, Ok(()));
         
         Ok(ExecutionResult {
             success: true,
-            profit_realized: Some(opportunity.estimated_profit),
+            profit_realized,
             error: None,
             transaction_signature: Some(signature),
-            execution_time_ms: execution_time,
+            execution_time_ms: latency.total_ms,
         })
     }
 
-    async fn add_market_state(&self, market: Pubkey) -> Result<(), ArbitrageError> {
-        let mut market_states = self.market_states.write().await;
-        
-        // Fetch market data and create MarketState
-        let market_state = self.fetch_market_state(market)?;
-        
-        market_states.push(market_state);
-        Ok(())
+    // Read the landed transaction back and work out its profit from the balance changes
+    fn measure_realized_pnl(
+        &self,
+        transaction: &Transaction,
+        wallet: &Pubkey,
+        signature: &str,
+    ) -> Result<(u64, RealizedPnl), ArbitrageError> {
+        let settings = self.settings.load();
+        let parsed = Signature::from_str(signature)
+            .map_err(|e| ArbitrageError::TransactionError(format!("Invalid signature {}: {}", signature, e)))?;
+        let request_start = Instant::now();
+        let confirmed = self.rpc_client.get_transaction_with_config(
+            &parsed,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        );
+        self.observe_rpc("getTransaction", request_start);
+        let confirmed = confirmed
+            .map_err(|e| ArbitrageError::NetworkError(format!("getTransaction failed for {}: {}", signature, e)))?;
+        let meta = confirmed
+            .transaction
+            .meta
+            .ok_or_else(|| ArbitrageError::TransactionError(format!("No status metadata for {}", signature)))?;
+
+        let base_mint = Pubkey::from_str(&settings.trading.inventory.base_mint)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Invalid base mint: {}", e)))?;
+        let tip_accounts = self.tip_accounts();
+
+        let (pnl, residue) = BalanceChanges::from_meta(&meta)?.realized_pnl(
+            &transaction.message.account_keys,
+            wallet,
+            &base_mint,
+            &tip_accounts,
+        )?;
+        for (mint, delta) in residue {
+            log::warn!("Trade {} left a balance change of {} in {} for {}", signature, delta, mint, wallet);
+        }
+
+        Ok((confirmed.slot, pnl))
+    }
+
+    fn settle_risk(&self, reservation: RiskReservation, pnl: i64) {
+        if let Err(e) = reservation.settle(pnl) {
+            log::error!("Failed to record trade with the risk manager: {}", e);
+        }
+    }
+
+    fn tip_accounts(&self) -> Vec<Pubkey> {
+        self.settings
+            .load()
+            .trading
+            .execution
+            .tip_accounts
+            .iter()
+            .filter_map(|account| Pubkey::from_str(account).ok())
+            .collect()
+    }
+
+    // SQLite writes block, so the journal is written on the blocking pool; failures are logged and never stop a
+    // trade. Dropping the handle leaves the write running
+    fn journal_decision(&self, record: OpportunityRecord) -> JoinHandle<Option<i64>> {
+        let journal = self.journal.clone();
+        tokio::task::spawn_blocking(move || match journal.record_opportunity(&record) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                log::error!("Failed to journal opportunity on {}: {}", record.source_market, e);
+                None
+            }
+        })
+    }
+
+    fn journal_execution(&self, mut record: ExecutionRecord, latency: Latency, outcome: Result<(), &ArbitrageError>) {
+        record.latency = latency;
+        match outcome {
+            Ok(()) => record.status = ExecutionStatus::Landed,
+            Err(e) => {
+                record.status = ExecutionStatus::Failed;
+                record.error = Some(e.to_string());
+            }
+        }
+
+        let journal = self.journal.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = journal.record_execution(&record) {
+                log::error!("Failed to journal execution on {}: {}", record.strategy, e);
+            }
+        });
+    }
+
+    // Journals why an opportunity was dropped; `label` is the fixed metric reason for it
+    fn rejected(&self, opportunity: &ArbitrageOpportunity, decision: Decision, reason: String, label: &str) {
+        self.metrics.opportunity_rejected(label);
+        self.journal_decision(OpportunityRecord::new(opportunity, decision, Some(reason)).with_label(label));
+    }
+
+    fn skipped(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        decision: Decision,
+        reason: &str,
+        label: &str,
+    ) -> ExecutionResult {
+        self.rejected(opportunity, decision, reason.to_string(), label);
+        ExecutionResult {
+            success: false,
+            profit_realized: None,
+            error: Some(reason.to_string()),
+            transaction_signature: None,
+            execution_time_ms: 0,
+        }
+    }
+
+    // One detection pass without trading, for the `scan` command
+    pub async fn scan(&self) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
+        self.sync_watched_markets().await?;
+        self.find_opportunities(&mut []).await
+    }
+
+    pub async fn watched_markets(&self) -> Result<Vec<MarketState>, ArbitrageError> {
+        self.sync_watched_markets().await?;
+        Ok(self.market_store.all().await)
+    }
+
+    // Loads one market regardless of the whitelist
+    pub async fn market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError> {
+        let snapshot = self.market_store.refresh(&[*market]).await?;
+        snapshot
+            .get(market)
+            .cloned()
+            .ok_or_else(|| ArbitrageError::MarketError(format!("Market {} could not be loaded", market)))
+    }
+
+    // Re-quotes the route against fresh market state, then builds and simulates it. The
+    // opportunity's age is not checked so saved opportunities can be replayed.
+    pub async fn dry_run(self: &Arc<Self>, opportunity: &ArbitrageOpportunity) -> Result<DryRun, ArbitrageError> {
+        let snapshot = self.market_store.refresh(&self.route_markets(opportunity)).await?;
+        let profitable_now = self.requote_opportunity(opportunity, &snapshot)?;
+
+        let lease = self
+            .wallets
+            .lease()
+            .ok_or_else(|| ArbitrageError::ConfigError("No execution wallet is free".to_string()))?;
+        let transaction = self.build_arbitrage_transaction(opportunity, lease.signer())?;
+
+        let simulation = self
+            .blocking(Span::current(), move |engine| {
+                let request_start = Instant::now();
+                let response = engine.rpc_client.simulate_transaction(&transaction);
+                engine.observe_rpc("simulateTransaction", request_start);
+                response.map_err(|e| ArbitrageError::NetworkError(format!("Failed to simulate transaction: {}", e)))
+            })
+            .await?
+            .value;
+
+        Ok(DryRun {
+            wallet: lease.pubkey(),
+            profitable_now,
+            simulation_error: simulation.err.map(|e| e.to_string()),
+            units_consumed: simulation.units_consumed,
+            logs: simulation.logs.unwrap_or_default(),
+        })
+    }
+
+    pub async fn latest_market_slot(&self) -> u64 {
+        self.market_store.latest_slot().await
+    }
+
+    // Estimated versus realized profit per strategy and per DEX
+    pub fn model_error(&self) -> BTreeMap<String, ModelErrorStats> {
+        self.model_error.report()
+    }
+
+    fn route_markets(&self, opportunity: &ArbitrageOpportunity) -> Vec<Pubkey> {
+        let mut markets: Vec<Pubkey> = Vec::new();
+        for step in &opportunity.route {
+            // Flash loan legs use a placeholder market and carry no price state
+            if step.market != Pubkey::default() && !markets.contains(&step.market) {
+                markets.push(step.market);
+            }
+        }
+        markets
+    }
+
+    fn requote_opportunity(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        snapshot: &MarketSnapshot,
+    ) -> Result<Option<Rejection>, ArbitrageError> {
+        if opportunity.required_amount == 0 {
+            return Ok(Some(Rejection::new("requote", "Route requires no input amount".to_string())));
+        }
+
+        let settings = self.settings.load();
+        let profit_calculator = ProfitCalculator::new(settings.clone());
+        let profit = profit_calculator.calculate_total_profit(opportunity, &snapshot.states)?;
+        let profit_ratio = profit / opportunity.required_amount as f64;
+
+        if profit_ratio < settings.trading.execution.min_profit_threshold {
+            log::debug!(
+                "Opportunity on {} no longer profitable at slot {}: {:.4}%",
+                opportunity.source_market,
+                snapshot.slot,
+                profit_ratio * 100.0
+            );
+            let reason = format!("No longer profitable at slot {}: {:.4}%", snapshot.slot, profit_ratio * 100.0);
+            return Ok(Some(Rejection::new("requote", reason)));
+        }
+
+        Ok(None)
+    }
+
+    // The RPC client blocks, so its calls run on the blocking pool instead of stalling a runtime worker
+    async fn blocking<T, F>(self: &Arc<Self>, span: Span, call: F) -> Result<T, ArbitrageError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&ArbitrageEngine) -> Result<T, ArbitrageError> + Send + 'static,
+    {
+        let engine = self.clone();
+        tokio::task::spawn_blocking(move || span.in_scope(|| call(&engine)))
+            .await
+            .map_err(|e| ArbitrageError::NetworkError(format!("Blocking RPC task failed: {}", e)))?
     }
 
-    fn fetch_market_state(&self, market: Pubkey) -> Result<MarketState, ArbitrageError> {
-        // Implement market state fetching logic
-        unimplemented!("Market state fetching not implemented")
+    fn get_current_slot(&self) -> Result<u64, ArbitrageError> {
+        let request_start = Instant::now();
+        let slot = self.rpc_client.get_slot();
+        self.observe_rpc("getSlot", request_start);
+        slot.map_err(|e| ArbitrageError::NetworkError(format!("Failed to fetch current slot: {}", e)))
+    }
+
+    fn observe_rpc(&self, method: &str, started: Instant) {
+        if self.settings.load().monitoring.performance_tracking {
+            self.metrics.observe_rpc(&self.rpc_client.url(), method, started.elapsed());
+        }
     }
 
     async fn get_whitelisted_markets(&self) -> Result<Vec<Pubkey>, ArbitrageError> {
         // Return markets from settings
-        Ok(self.settings.trading.markets.whitelisted_markets
+        let settings = self.settings.load();
+        let markets = &settings.trading.markets;
+        Ok(markets.whitelisted_markets
             .iter()
+            .filter(|m| !markets.blacklisted_markets.contains(m))
             .filter_map(|m| Pubkey::from_str(m).ok())
             .collect())
     }
 
     fn check_security_constraints(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
-        // Implement security checks (MEV protection, quantum security, etc.)
+        // Implement security checks (MEV protection, transaction guards, etc.)
         Ok(true)
     }
 
-    fn build_arbitrage_transaction(&self, opportunity: &ArbitrageOpportunity) -> Result<Transaction, ArbitrageError> {
-        // Implement transaction building logic
-        unimplemented!("Transaction building not implemented")
+    fn build_arbitrage_transaction(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        signer: SharedSigner,
+    ) -> Result<Transaction, ArbitrageError> {
+        TransactionBuilder::new(self.settings.load(), signer)
+            .build_arbitrage_transaction(opportunity)
     }
 
     fn simulate_transaction(&self, transaction: &Transaction) -> Result<bool, ArbitrageError> {
@@ -296,8 +1323,337 @@ impl ArbitrageEngine {
         unimplemented!("Transaction confirmation not implemented")
     }
 
-    fn validate_market_state(&self, market_states: &[MarketState], market: &Pubkey) -> Result<bool, ArbitrageError> {
-        // Implement market state validation logic
-        Ok(true)
+    fn validate_market_state(
+        &self,
+        snapshot: &MarketSnapshot,
+        market: &Pubkey,
+        current_slot: u64,
+    ) -> Option<Rejection> {
+        let market_state = match snapshot.get(market) {
+            Some(state) => state,
+            None => return Some(Rejection::new("stale_market", format!("No state for market {}", market))),
+        };
+
+        // Reject inputs that are older than the configured slot threshold
+        let max_age = self.settings.load().trading.markets.max_state_age_slots;
+        let age = current_slot.saturating_sub(market_state.slot);
+        self.metrics.observe_market_lag(age);
+        if age > max_age {
+            log::debug!(
+                "Market {} is {} slots stale (limit {})",
+                market,
+                age,
+                max_age
+            );
+            self.alerts.send(
+                Alert::new(
+                    AlertSeverity::Warning,
+                    "market_data",
+                    format!("Market {} data is {} slots stale (limit {})", market, age, max_age),
+                )
+                .with_key(market.to_string()),
+            );
+            let reason = format!("Market {} is {} slots stale (limit {})", market, age, max_age);
+            return Some(Rejection::new("stale_market", reason));
+        }
+
+        None
+    }
+}
+
+// A failing strategy only loses its own opportunities for the cycle; what it finds is tagged with its control key
+// so pauses, freshness and metrics apply to it like to the engine's routes
+fn scan_factory(
+    factory: &mut [(String, Box<dyn ArbitrageStrategy>)],
+    settings: Arc<Settings>,
+    market_states: Vec<MarketState>,
+    markets: &[Pubkey],
+) -> Vec<ArbitrageOpportunity> {
+    let market_states = Arc::new(market_states);
+    let mut opportunities = Vec::new();
+    for (key, strategy) in factory.iter_mut() {
+        strategy.refresh(settings.clone(), market_states.clone());
+        match strategy.analyze(markets) {
+            Ok(found) => opportunities.extend(found.into_iter().map(|mut opportunity| {
+                opportunity.strategy = key.clone();
+                opportunity
+            })),
+            Err(e) => log::warn!("Strategy {} failed to scan: {}", key, e),
+        }
+    }
+    opportunities
+}
+
+// Rejects opportunities older than their strategy's freshness window
+fn stale_opportunity(
+    opportunity: &ArbitrageOpportunity,
+    current_slot: u64,
+    freshness: &FreshnessSettings,
+) -> Option<Rejection> {
+    let max_age = freshness.for_strategy(&opportunity.strategy);
+    let age = current_slot.saturating_sub(opportunity.slot);
+    if age > max_age {
+        let reason = format!("Opportunity is {} slots old (limit {} for {})", age, max_age, opportunity.strategy);
+        return Some(Rejection::new("stale_opportunity", reason));
+    }
+    None
+}
+
+// Journal entry for a trade about to be sent; the outcome is filled in as it progresses
+fn execution_record(
+    opportunity: &ArbitrageOpportunity,
+    opportunity_id: Option<i64>,
+    transaction: &Transaction,
+    wallet: &Pubkey,
+) -> ExecutionRecord {
+    ExecutionRecord {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64,
+        opportunity_id,
+        strategy: opportunity.strategy.clone(),
+        token: opportunity.token_pair.base_token.address.to_string(),
+        route_shape: route_shape(&opportunity.route),
+        venues: route_venues(transaction).iter().map(|venue| venue.to_string()).collect(),
+        wallet: wallet.to_string(),
+        notional: opportunity.required_amount,
+        signature: None,
+        slot: None,
+        status: ExecutionStatus::Failed,
+        error: None,
+        estimated_profit: opportunity.estimated_profit as i64,
+        realized_profit: None,
+        gross_profit: None,
+        fee: 0,
+        tip: 0,
+        rent: 0,
+        latency: Latency::default(),
+    }
+}
+
+// Lamports the fee payer pays whether or not the transaction succeeds: signature fees plus the priority fee
+fn transaction_fee(transaction: &Transaction) -> u64 {
+    let message = &transaction.message;
+    let mut unit_price = 0u64;
+    let mut unit_limit = None;
+    for instruction in &message.instructions {
+        if message.account_keys[instruction.program_id_index as usize] != compute_budget::id() {
+            continue;
+        }
+        match instruction.data.split_first() {
+            Some((&SET_COMPUTE_UNIT_LIMIT, limit)) if limit.len() >= 4 => {
+                unit_limit = Some(u32::from_le_bytes(limit[..4].try_into().unwrap()) as u64);
+            }
+            Some((&SET_COMPUTE_UNIT_PRICE, price)) if price.len() >= 8 => {
+                unit_price = u64::from_le_bytes(price[..8].try_into().unwrap());
+            }
+            _ => {}
+        }
+    }
+
+    let unit_limit = unit_limit
+        .unwrap_or(DEFAULT_INSTRUCTION_COMPUTE_UNITS * message.instructions.len() as u64)
+        .min(MAX_COMPUTE_UNITS);
+    // The unit price is in micro-lamports
+    let priority_fee = ((unit_price as u128 * unit_limit as u128 + 999_999) / 1_000_000) as u64;
+
+    message.header.num_required_signatures as u64 * LAMPORTS_PER_SIGNATURE + priority_fee
+}
+
+// Lamports the transaction transfers to block-engine tip accounts; only paid if it lands
+fn transaction_tips(transaction: &Transaction, tip_accounts: &[Pubkey]) -> u64 {
+    let message = &transaction.message;
+    message
+        .instructions
+        .iter()
+        .filter(|instruction| message.account_keys[instruction.program_id_index as usize] == system_program::id())
+        .filter(|instruction| {
+            instruction
+                .accounts
+                .get(1)
+                .map_or(false, |index| tip_accounts.contains(&message.account_keys[*index as usize]))
+        })
+        .filter_map(|instruction| match bincode::deserialize::<SystemInstruction>(&instruction.data) {
+            Ok(SystemInstruction::Transfer { lamports }) => Some(lamports),
+            _ => None,
+        })
+        .sum()
+}
+
+// DEX programs the transaction invokes, in route order
+fn route_venues(transaction: &Transaction) -> Vec<&'static str> {
+    let mut venues = Vec::new();
+    for instruction in &transaction.message.instructions {
+        let program_id = &transaction.message.account_keys[instruction.program_id_index as usize];
+        if let Some(venue) = venue_name(program_id) {
+            if !venues.contains(&venue) {
+                venues.push(venue);
+            }
+        }
+    }
+    venues
+}
+
+struct EngineMarketDecoder;
+
+impl MarketDecoder for EngineMarketDecoder {
+    fn decode(
+        &self,
+        market: &Pubkey,
+        account: &Account,
+        slot: u64,
+    ) -> Result<MarketState, ArbitrageError> {
+        // No venue layout is decoded yet; refuse the market rather than take down the caller
+        Err(ArbitrageError::MarketError(format!(
+            "Market {} at slot {}: no decoder for accounts owned by {} ({})",
+            market,
+            slot,
+            account.owner,
+            venue_name(&account.owner).unwrap_or("unknown venue")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{compute_budget::ComputeBudgetInstruction, message::Message, system_instruction},
+    };
+
+    #[test]
+    fn test_failed_trade_cost_counts_priority_fee_and_landed_tips() {
+        let payer = Pubkey::new_unique();
+        let tip_account = Pubkey::new_unique();
+        let transaction = Transaction::new_unsigned(Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+                ComputeBudgetInstruction::set_compute_unit_price(2_000_000),
+                system_instruction::transfer(&payer, &tip_account, 10_000),
+            ],
+            Some(&payer),
+        ));
+
+        // One signature plus 100k units at 2 lamports each
+        assert_eq!(transaction_fee(&transaction), 5_000 + 200_000);
+        assert_eq!(transaction_tips(&transaction, &[tip_account]), 10_000);
+        assert_eq!(transaction_tips(&transaction, &[]), 0);
+    }
+
+    #[test]
+    fn test_factory_strategies_do_not_shadow_engine_routes() {
+        let factory = crate::strategies::StrategyFactory::create_strategy("flash_loan").unwrap();
+        let controls = EngineControls::new(vec![("flash_loan".to_string(), factory)]);
+
+        assert_eq!(controls.strategies["flash_loan"].source, "engine");
+        assert_eq!(controls.strategies["factory:flash_loan"].source, "factory");
+        assert_eq!(controls.strategies.len(), ENGINE_STRATEGIES.len() + 1);
+    }
+
+    fn opportunity(strategy: &str, slot: u64) -> ArbitrageOpportunity {
+        let token = crate::types::common::Token {
+            address: Pubkey::new_unique(),
+            symbol: String::new(),
+            decimals: 6,
+        };
+        ArbitrageOpportunity {
+            source_market: Pubkey::new_unique(),
+            target_market: Pubkey::new_unique(),
+            token_pair: TokenPair {
+                base_token: token.clone(),
+                quote_token: token,
+            },
+            profit_percentage: 0.0,
+            required_amount: 1_000,
+            estimated_profit: 0,
+            route: Vec::new(),
+            timestamp: 0,
+            slot,
+            strategy: strategy.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_opportunity_freshness_uses_the_strategy_window() {
+        let freshness = FreshnessSettings {
+            direct: 4,
+            triangular: 4,
+            jit: 4,
+            flash_loan: 2,
+            front_running: 1,
+        };
+
+        // Exactly at the limit is still fresh
+        assert!(stale_opportunity(&opportunity("direct", 100), 104, &freshness).is_none());
+        let rejection = stale_opportunity(&opportunity("direct", 100), 105, &freshness).unwrap();
+        assert_eq!(rejection.label, "stale_opportunity");
+
+        assert!(stale_opportunity(&opportunity("flash_loan", 100), 103, &freshness).is_some());
+        assert!(stale_opportunity(&opportunity("factory:front_running", 100), 102, &freshness).is_some());
+        assert!(stale_opportunity(&opportunity("factory:sandwich", 100), 102, &freshness).is_some());
+        // An opportunity from a slot ahead of the node's view is not stale
+        assert!(stale_opportunity(&opportunity("front_running", 100), 90, &freshness).is_none());
+    }
+
+    // Finds one opportunity per market it has state for and fails when it has none
+    struct StubStrategy;
+
+    impl ArbitrageStrategy for StubStrategy {
+        fn name(&self) -> &'static str {
+            "Stub Strategy"
+        }
+
+        fn refresh(&mut self, _settings: Arc<Settings>, _market_states: Arc<Vec<MarketState>>) {}
+
+        fn analyze(&self, markets: &[Pubkey]) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
+            if markets.is_empty() {
+                return Err(ArbitrageError::MarketError("No markets".to_string()));
+            }
+            Ok(markets.iter().map(|_| opportunity("jit", 0)).collect())
+        }
+
+        fn execute(&self, _opportunity: &ArbitrageOpportunity) -> Result<ExecutionResult, ArbitrageError> {
+            unimplemented!()
+        }
+
+        fn validate(&self, _opportunity: &ArbitrageOpportunity, _current_slot: u64) -> Result<bool, ArbitrageError> {
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn test_factory_opportunities_are_tagged_and_pausable() {
+        let stub: Box<dyn ArbitrageStrategy> = Box::new(StubStrategy);
+        let mut controls = EngineControls::new(vec![("jit".to_string(), stub)]);
+        let settings = Arc::new(Settings::default());
+        let markets = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let found = scan_factory(&mut controls.factory, settings.clone(), Vec::new(), &markets);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|opportunity| opportunity.strategy == "factory:jit"));
+        assert!(controls.paused(&found[0], &[]).is_none());
+
+        controls.strategies.get_mut("factory:jit").unwrap().paused = true;
+        assert!(controls.paused(&found[0], &[]).is_some());
+
+        // A failing strategy yields nothing rather than failing the cycle
+        assert!(scan_factory(&mut controls.factory, settings, Vec::new(), &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_trade_slots_until_deadline() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let slot = TradeSlot::acquire(&in_flight);
+        let stuck = TradeSlot::acquire(&in_flight);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            drop(slot);
+        });
+        assert_eq!(drain_in_flight(&in_flight, Duration::from_millis(500)).await, 1);
+
+        drop(stuck);
+        assert_eq!(drain_in_flight(&in_flight, Duration::from_secs(5)).await, 0);
     }
 }
@@ -0,0 +1,302 @@
+use {
+    crate::{
+        core::MarketDecoder,
+        types::common::{ArbitrageError, MarketState},
+    },
+    solana_client::{rpc_client::RpcClient, rpc_config::RpcAccountInfoConfig},
+    solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey},
+    std::{collections::HashMap, sync::Arc},
+    tokio::sync::RwLock,
+};
+
+pub struct MarketStore {
+    rpc_client: Arc<RpcClient>,
+    decoder: Arc<dyn MarketDecoder>,
+    states: RwLock<HashMap<Pubkey, MarketState>>,
+    max_slot_spread: u64,
+}
+
+// A view over several markets that was observed at a consistent point in time
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    pub slot: u64,
+    pub states: Vec<MarketState>,
+}
+
+impl MarketSnapshot {
+    pub fn get(&self, market: &Pubkey) -> Option<&MarketState> {
+        self.states.iter().find(|state| state.market_address == *market)
+    }
+
+    pub fn oldest_slot(&self) -> u64 {
+        self.states.iter().map(|state| state.slot).min().unwrap_or(self.slot)
+    }
+}
+
+impl MarketStore {
+    pub fn new(
+        rpc_client: Arc<RpcClient>,
+        decoder: Arc<dyn MarketDecoder>,
+        max_slot_spread: u64,
+    ) -> Self {
+        Self {
+            rpc_client,
+            decoder,
+            states: RwLock::new(HashMap::new()),
+            max_slot_spread,
+        }
+    }
+
+    pub async fn update(&self, state: MarketState) {
+        let mut states = self.states.write().await;
+
+        // Never let an out-of-order update overwrite a newer observation
+        match states.get(&state.market_address) {
+            Some(existing) if existing.slot > state.slot => {}
+            _ => {
+                states.insert(state.market_address, state);
+            }
+        }
+    }
+
+    pub async fn get(&self, market: &Pubkey) -> Option<MarketState> {
+        self.states.read().await.get(market).cloned()
+    }
+
+    pub async fn all(&self) -> Vec<MarketState> {
+        self.states.read().await.values().cloned().collect()
+    }
+
+    pub async fn latest_slot(&self) -> u64 {
+        self.states
+            .read()
+            .await
+            .values()
+            .map(|state| state.slot)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub async fn snapshot(
+        &self,
+        accounts: &[Pubkey],
+        min_slot: u64,
+    ) -> Result<MarketSnapshot, ArbitrageError> {
+        let (cached, highest_slot) = {
+            let states = self.states.read().await;
+            let cached: Vec<Option<MarketState>> = accounts
+                .iter()
+                .map(|account| states.get(account).cloned())
+                .collect();
+            let highest_slot = cached
+                .iter()
+                .flatten()
+                .map(|state| state.slot)
+                .max()
+                .unwrap_or(0);
+            (cached, highest_slot)
+        };
+
+        // Serve from the cache when every account is present and observed close enough together
+        if let Some(states) = cached.into_iter().collect::<Option<Vec<_>>>() {
+            if self.is_consistent(&states, min_slot) {
+                let slot = states.iter().map(|state| state.slot).min().unwrap_or(min_slot);
+                return Ok(MarketSnapshot { slot, states });
+            }
+        }
+
+        // Otherwise pin a single read at a slot no older than anything we have seen
+        self.refetch(accounts, min_slot.max(highest_slot)).await
+    }
+
+    pub async fn refresh(&self, accounts: &[Pubkey]) -> Result<MarketSnapshot, ArbitrageError> {
+        self.refetch(accounts, 0).await
+    }
+
+    // Caches every market that can be read; returns the ones that are missing or undecodable instead of failing
+    // the rest with them
+    pub async fn load(&self, accounts: &[Pubkey]) -> Result<Vec<(Pubkey, ArbitrageError)>, ArbitrageError> {
+        let (_, results) = self.fetch(accounts, 0).await?;
+
+        Ok(accounts
+            .iter()
+            .zip(results)
+            .filter_map(|(market, result)| result.err().map(|error| (*market, error)))
+            .collect())
+    }
+
+    fn is_consistent(&self, states: &[MarketState], min_slot: u64) -> bool {
+        let oldest = states.iter().map(|state| state.slot).min().unwrap_or(0);
+        let newest = states.iter().map(|state| state.slot).max().unwrap_or(0);
+
+        oldest >= min_slot && newest - oldest <= self.max_slot_spread
+    }
+
+    // A snapshot needs every account, so one that cannot be read fails it
+    async fn refetch(
+        &self,
+        accounts: &[Pubkey],
+        pinned_slot: u64,
+    ) -> Result<MarketSnapshot, ArbitrageError> {
+        let (slot, results) = self.fetch(accounts, pinned_slot).await?;
+        let states = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+        Ok(MarketSnapshot { slot, states })
+    }
+
+    // One result per account, in order; only the request itself failing is an error
+    async fn fetch(
+        &self,
+        accounts: &[Pubkey],
+        pinned_slot: u64,
+    ) -> Result<(u64, Vec<Result<MarketState, ArbitrageError>>), ArbitrageError> {
+        let config = RpcAccountInfoConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+            min_context_slot: if pinned_slot > 0 { Some(pinned_slot) } else { None },
+            ..RpcAccountInfoConfig::default()
+        };
+
+        // The RPC client blocks, so the request runs on the blocking pool
+        let rpc_client = self.rpc_client.clone();
+        let keys = accounts.to_vec();
+        let response = tokio::task::spawn_blocking(move || rpc_client.get_multiple_accounts_with_config(&keys, config))
+            .await
+            .map_err(|e| ArbitrageError::NetworkError(format!("getMultipleAccounts task failed: {}", e)))?
+            .map_err(|e| ArbitrageError::NetworkError(format!("getMultipleAccounts failed: {}", e)))?;
+
+        // Every account in the response was read at the same context slot
+        let slot = response.context.slot;
+        Ok((slot, self.ingest(accounts, response.value, slot).await))
+    }
+
+    // Decodes and caches the fetched accounts; a missing or undecodable one leaves the others untouched
+    async fn ingest(
+        &self,
+        accounts: &[Pubkey],
+        fetched: Vec<Option<Account>>,
+        slot: u64,
+    ) -> Vec<Result<MarketState, ArbitrageError>> {
+        let mut results = Vec::with_capacity(accounts.len());
+
+        for (market, account) in accounts.iter().zip(fetched) {
+            let result = match account {
+                Some(account) => self.decoder.decode(market, &account, slot),
+                None => Err(ArbitrageError::MarketError(format!("Market account {} not found", market))),
+            };
+            if let Ok(state) = &result {
+                self.update(state.clone()).await;
+            }
+            results.push(result);
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::types::common::Token};
+
+    // Decodes any account with data; an empty one stands in for an unknown layout
+    struct TestDecoder;
+
+    impl MarketDecoder for TestDecoder {
+        fn decode(&self, market: &Pubkey, account: &Account, slot: u64) -> Result<MarketState, ArbitrageError> {
+            if account.data.is_empty() {
+                return Err(ArbitrageError::MarketError(format!("Cannot decode {}", market)));
+            }
+            Ok(state(*market, slot))
+        }
+    }
+
+    fn state(market: Pubkey, slot: u64) -> MarketState {
+        let token = Token {
+            address: Pubkey::new_unique(),
+            symbol: String::new(),
+            decimals: 9,
+        };
+        MarketState {
+            market_address: market,
+            base_token: token.clone(),
+            quote_token: token,
+            best_bid: 1.0,
+            best_ask: 1.0,
+            last_update: 0,
+            slot,
+        }
+    }
+
+    fn store(max_slot_spread: u64) -> MarketStore {
+        let rpc_client = Arc::new(RpcClient::new("http://127.0.0.1:1".to_string()));
+        MarketStore::new(rpc_client, Arc::new(TestDecoder), max_slot_spread)
+    }
+
+    fn account(data: Vec<u8>) -> Account {
+        Account {
+            data,
+            ..Account::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_account_does_not_fail_the_others() {
+        let store = store(2);
+        let (good, missing, undecodable) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let results = store
+            .ingest(&[good, missing, undecodable], vec![Some(account(vec![1])), None, Some(account(vec![]))], 10)
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().slot, 10);
+        assert!(results[1].is_err());
+        assert!(results[2].is_err());
+        assert_eq!(store.get(&good).await.map(|state| state.slot), Some(10));
+        assert!(store.get(&missing).await.is_none());
+        assert!(store.get(&undecodable).await.is_none());
+    }
+
+    #[test]
+    fn test_consistency_needs_min_slot_and_a_narrow_spread() {
+        let store = store(2);
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(store.is_consistent(&[state(a, 100), state(b, 102)], 100));
+        // Spread of three slots
+        assert!(!store.is_consistent(&[state(a, 100), state(b, 103)], 100));
+        // Older than required
+        assert!(!store.is_consistent(&[state(a, 100), state(b, 101)], 101));
+    }
+
+    #[tokio::test]
+    async fn test_update_ignores_older_slots() {
+        let store = store(2);
+        let market = Pubkey::new_unique();
+
+        store.update(state(market, 10)).await;
+        store.update(state(market, 8)).await;
+        assert_eq!(store.get(&market).await.map(|state| state.slot), Some(10));
+
+        store.update(state(market, 12)).await;
+        assert_eq!(store.get(&market).await.map(|state| state.slot), Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_serves_consistent_cache_and_refetches_otherwise() {
+        // Nothing listens on the RPC endpoint, so a refetch fails with a network error
+        let store = store(2);
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        store.update(state(a, 100)).await;
+        store.update(state(b, 101)).await;
+
+        let snapshot = store.snapshot(&[a, b], 100).await.unwrap();
+        assert_eq!(snapshot.slot, 100);
+        assert_eq!(snapshot.get(&b).map(|state| state.slot), Some(101));
+
+        // Cached states older than required
+        assert!(matches!(store.snapshot(&[a, b], 101).await, Err(ArbitrageError::NetworkError(_))));
+        // A market that was never cached
+        let missing = Pubkey::new_unique();
+        assert!(matches!(store.snapshot(&[a, missing], 0).await, Err(ArbitrageError::NetworkError(_))));
+    }
+}
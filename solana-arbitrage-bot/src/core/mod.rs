@@ -1,22 +1,33 @@
+mod account_locks;
 mod arbitrage_engine;
+mod market_store;
 mod profit_calculator;
+mod realized_pnl;
 mod transaction_builder;
 
+pub use account_locks::*;
 pub use arbitrage_engine::*;
+pub use market_store::*;
 pub use profit_calculator::*;
+pub use realized_pnl::*;
 pub use transaction_builder::*;
 
-use crate::types::common::{ArbitrageError, ArbitrageOpportunity, ExecutionResult};
-use solana_sdk::pubkey::Pubkey;
+use crate::config::Settings;
+use crate::types::common::{ArbitrageError, ArbitrageOpportunity, ExecutionResult, MarketState};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::sync::Arc;
 
-pub trait ArbitrageStrategy {
+pub trait ArbitrageStrategy: Send + Sync {
     fn name(&self) -> &'static str;
+
+    // Called by the engine before each scan so the strategy quotes the same markets and settings it does
+    fn refresh(&mut self, settings: Arc<Settings>, market_states: Arc<Vec<MarketState>>);
     
     fn analyze(&self, markets: &[Pubkey]) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError>;
     
     fn execute(&self, opportunity: &ArbitrageOpportunity) -> Result<ExecutionResult, ArbitrageError>;
     
-    fn validate(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError>;
+    fn validate(&self, opportunity: &ArbitrageOpportunity, current_slot: u64) -> Result<bool, ArbitrageError>;
 }
 
 pub trait ProfitCalculator {
@@ -40,3 +51,12 @@ pub trait TransactionBuilder {
         transaction: &[u8],
     ) -> Result<bool, ArbitrageError>;
 }
+
+pub trait MarketDecoder: Send + Sync {
+    fn decode(
+        &self,
+        market: &Pubkey,
+        account: &Account,
+        slot: u64,
+    ) -> Result<MarketState, ArbitrageError>;
+}
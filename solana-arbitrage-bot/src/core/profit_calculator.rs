@@ -12,10 +12,8 @@ pub struct ProfitCalculator {
 }
 
 impl ProfitCalculator {
-    pub fn new(settings: Settings) -> Self {
-        Self {
-            settings: Arc::new(settings),
-        }
+    pub fn new(settings: Arc<Settings>) -> Self {
+        Self { settings }
     }
 
     pub fn calculate_total_profit(
@@ -28,6 +26,10 @@ impl ProfitCalculator {
 
         // Calculate profit for each step in the arbitrage route
         for step in &opportunity.route {
+            // Flash loan legs use a placeholder market with no price state; their fee is counted below
+            if step.market == Pubkey::default() {
+                continue;
+            }
             let (profit, new_amount) = self.calculate_step_profit(step, current_amount, market_states)?;
             total_profit += profit;
             current_amount = new_amount;
@@ -82,7 +84,7 @@ impl ProfitCalculator {
         Ok(total_cost)
     }
 
-    pub fn calculate_total_fees(&self, opportunity: &ArbitrageOpportunity) -> Result<f64, ArbigrageError> {
+    pub fn calculate_total_fees(&self, opportunity: &ArbitrageOpportunity) -> Result<f64, ArbitrageError> {
         let mut total_fees = 0.0;
 
         // Trading fees
@@ -226,9 +228,9 @@ impl ProfitCalculator {
             return Ok(false);
         }
 
-        // Check profit vs risk ratio
+        // Check profit vs risk ratio; the threshold is a loss fraction, so compare against its negation
         let risk_ratio = profit / opportunity.required_amount as f64;
-        if risk_ratio < self.settings.trading.risk.max_loss_threshold {
+        if risk_ratio < -self.settings.trading.risk.max_loss_threshold {
             return Ok(false);
         }
 
@@ -0,0 +1,246 @@
+use {
+    crate::types::common::ArbitrageError,
+    solana_sdk::pubkey::Pubkey,
+    solana_transaction_status::{
+        option_serializer::OptionSerializer, UiTransactionStatusMeta, UiTransactionTokenBalance,
+    },
+    std::{collections::BTreeMap, str::FromStr, sync::Mutex},
+};
+
+// Outcome of a landed trade as observed on chain, in base units
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RealizedPnl {
+    // Before fees, tips and rent
+    pub gross: i64,
+    pub fee: u64,
+    pub tip: u64,
+    // Lamports moved into accounts the transaction created
+    pub rent: u64,
+    pub net: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBalance {
+    pub account_index: usize,
+    pub mint: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub amount: u64,
+}
+
+// Pre/post balances of every account the transaction touched
+#[derive(Debug, Clone, Default)]
+pub struct BalanceChanges {
+    pub fee: u64,
+    pub pre_lamports: Vec<u64>,
+    pub post_lamports: Vec<u64>,
+    pub pre_tokens: Vec<TokenBalance>,
+    pub post_tokens: Vec<TokenBalance>,
+}
+
+impl BalanceChanges {
+    pub fn from_meta(meta: &UiTransactionStatusMeta) -> Result<Self, ArbitrageError> {
+        Ok(Self {
+            fee: meta.fee,
+            pre_lamports: meta.pre_balances.clone(),
+            post_lamports: meta.post_balances.clone(),
+            pre_tokens: token_balances(&meta.pre_token_balances)?,
+            post_tokens: token_balances(&meta.post_token_balances)?,
+        })
+    }
+
+    // Lamport change of the wallet plus its change in `base_mint`, split into fee, tip and rent.
+    // Every other token should net to zero over a route; whatever does not is returned as residue.
+    pub fn realized_pnl(
+        &self,
+        account_keys: &[Pubkey],
+        wallet: &Pubkey,
+        base_mint: &Pubkey,
+        tip_accounts: &[Pubkey],
+    ) -> Result<(RealizedPnl, Vec<(Pubkey, i64)>), ArbitrageError> {
+        let wallet_index = account_keys
+            .iter()
+            .position(|key| key == wallet)
+            .ok_or_else(|| ArbitrageError::TransactionError(format!("Wallet {} is not in the transaction", wallet)))?;
+        if self.pre_lamports.len() != account_keys.len() || self.post_lamports.len() != account_keys.len() {
+            return Err(ArbitrageError::TransactionError(format!(
+                "Transaction metadata has {} balances for {} accounts",
+                self.post_lamports.len(),
+                account_keys.len()
+            )));
+        }
+
+        let lamport_delta = self.post_lamports[wallet_index] as i64 - self.pre_lamports[wallet_index] as i64;
+
+        let mut tip = 0;
+        let mut rent = 0;
+        for (index, key) in account_keys.iter().enumerate() {
+            let (pre, post) = (self.pre_lamports[index], self.post_lamports[index]);
+            if tip_accounts.contains(key) {
+                tip += post.saturating_sub(pre);
+            } else if index != wallet_index && pre == 0 && post > 0 {
+                rent += post;
+            }
+        }
+
+        let mut deltas: BTreeMap<Pubkey, i64> = BTreeMap::new();
+        for (balances, sign) in [(&self.pre_tokens, -1), (&self.post_tokens, 1)] {
+            for balance in balances.iter().filter(|balance| balance.owner.as_ref() == Some(wallet)) {
+                *deltas.entry(balance.mint).or_default() += sign * balance.amount as i64;
+            }
+        }
+
+        let net = lamport_delta + deltas.remove(base_mint).unwrap_or(0);
+        let residue = deltas.into_iter().filter(|(_, delta)| *delta != 0).collect();
+
+        Ok((
+            RealizedPnl {
+                gross: net + (self.fee + tip + rent) as i64,
+                fee: self.fee,
+                tip,
+                rent,
+                net,
+            },
+            residue,
+        ))
+    }
+}
+
+fn token_balances(
+    balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+) -> Result<Vec<TokenBalance>, ArbitrageError> {
+    let balances = match balances {
+        OptionSerializer::Some(balances) => balances,
+        _ => return Ok(Vec::new()),
+    };
+
+    balances
+        .iter()
+        .map(|balance| {
+            let invalid = |field: &str| {
+                ArbitrageError::TransactionError(format!("Malformed token balance {}: {}", field, balance.account_index))
+            };
+            Ok(TokenBalance {
+                account_index: balance.account_index as usize,
+                mint: Pubkey::from_str(&balance.mint).map_err(|_| invalid("mint"))?,
+                owner: match &balance.owner {
+                    OptionSerializer::Some(owner) => Some(Pubkey::from_str(owner).map_err(|_| invalid("owner"))?),
+                    _ => None,
+                },
+                amount: balance.ui_token_amount.amount.parse().map_err(|_| invalid("amount"))?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ModelErrorStats {
+    pub trades: u64,
+    pub estimated: i64,
+    pub realized: i64,
+    pub absolute_error: u64,
+}
+
+impl ModelErrorStats {
+    // Positive when trades land above their estimate
+    pub fn mean_error(&self) -> f64 {
+        (self.realized - self.estimated) as f64 / self.trades.max(1) as f64
+    }
+
+    pub fn mean_absolute_error(&self) -> f64 {
+        self.absolute_error as f64 / self.trades.max(1) as f64
+    }
+}
+
+// Estimated versus realized profit, keyed by `strategy:<name>` and `dex:<name>`
+#[derive(Default)]
+pub struct ModelErrorTracker {
+    stats: Mutex<BTreeMap<String, ModelErrorStats>>,
+}
+
+impl ModelErrorTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, scopes: &[String], estimated: i64, realized: i64) {
+        let mut stats = self.stats.lock().unwrap();
+        for scope in scopes {
+            let entry = stats.entry(scope.clone()).or_default();
+            entry.trades += 1;
+            entry.estimated += estimated;
+            entry.realized += realized;
+            entry.absolute_error += (realized - estimated).unsigned_abs();
+        }
+    }
+
+    pub fn get(&self, scope: &str) -> Option<ModelErrorStats> {
+        self.stats.lock().unwrap().get(scope).copied()
+    }
+
+    pub fn report(&self) -> BTreeMap<String, ModelErrorStats> {
+        self.stats.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_realized_pnl_nets_fee_tip_and_rent() {
+        let wallet = Pubkey::new_unique();
+        let new_account = Pubkey::new_unique();
+        let tip_account = Pubkey::new_unique();
+        let base_mint = Pubkey::new_unique();
+        let other_mint = Pubkey::new_unique();
+        let token = |mint, amount| TokenBalance {
+            account_index: 1,
+            mint,
+            owner: Some(wallet),
+            amount,
+        };
+
+        // Paid 5_000 fee, 10_000 tip and 2_039_280 rent; gained 2_100_000 of the base token
+        let changes = BalanceChanges {
+            fee: 5_000,
+            pre_lamports: vec![10_000_000, 0, 1_000],
+            post_lamports: vec![10_000_000 - 5_000 - 10_000 - 2_039_280, 2_039_280, 11_000],
+            pre_tokens: vec![token(base_mint, 1_000_000), token(other_mint, 0)],
+            post_tokens: vec![token(base_mint, 3_100_000), token(other_mint, 7)],
+        };
+
+        let (pnl, residue) = changes
+            .realized_pnl(&[wallet, new_account, tip_account], &wallet, &base_mint, &[tip_account])
+            .unwrap();
+
+        assert_eq!(
+            pnl,
+            RealizedPnl {
+                gross: 2_100_000,
+                fee: 5_000,
+                tip: 10_000,
+                rent: 2_039_280,
+                net: 2_100_000 - 5_000 - 10_000 - 2_039_280,
+            }
+        );
+        assert_eq!(residue, vec![(other_mint, 7)]);
+    }
+
+    #[test]
+    fn test_model_error_tracks_each_scope() {
+        let tracker = ModelErrorTracker::new();
+        let scopes = ["strategy:jit".to_string(), "dex:orca".to_string()];
+
+        tracker.record(&scopes, 100, 80);
+        tracker.record(&scopes[1..], 100, 130);
+
+        let jit = tracker.get("strategy:jit").unwrap();
+        assert_eq!(jit.trades, 1);
+        assert_eq!(jit.mean_error(), -20.0);
+
+        let orca = tracker.get("dex:orca").unwrap();
+        assert_eq!(orca.trades, 2);
+        assert_eq!(orca.mean_error(), 5.0);
+        assert_eq!(orca.mean_absolute_error(), 25.0);
+    }
+}
@@ -5,12 +5,13 @@ use {
             TradeStep, TradeSide,
         },
         config::Settings,
+        signer::SharedSigner,
     },
     solana_sdk::{
         instruction::Instruction,
         message::Message,
         pubkey::Pubkey,
-        signature::{Keypair, Signer},
+        signature::Signer,
         transaction::Transaction,
         system_instruction,
     },
@@ -19,15 +20,12 @@ use {
 
 pub struct TransactionBuilder {
     settings: Arc<Settings>,
-    keypair: Arc<Keypair>,
+    signer: SharedSigner,
 }
 
 impl TransactionBuilder {
-    pub fn new(settings: Settings, keypair: Keypair) -> Self {
-        Self {
-            settings: Arc::new(settings),
-            keypair: Arc::new(keypair),
-        }
+    pub fn new(settings: Arc<Settings>, signer: SharedSigner) -> Self {
+        Self { settings, signer }
     }
 
     pub fn build_arbitrage_transaction(
@@ -158,11 +156,14 @@ impl TransactionBuilder {
         instructions: Vec<Instruction>,
     ) -> Result<Transaction, ArbitrageError> {
         // Create message
-        let message = Message::new(&instructions, Some(&self.keypair.pubkey()));
+        let message = Message::new(&instructions, Some(&self.signer.pubkey()));
 
-        // Create and sign transaction
+        // Create and sign transaction; a remote signer may refuse, so surface that as an error
         let mut transaction = Transaction::new_unsigned(message);
-        transaction.sign(&[&self.keypair], transaction.message.recent_blockhash);
+        let signers: [Arc<dyn Signer>; 1] = [self.signer.clone()];
+        transaction
+            .try_sign(&signers, transaction.message.recent_blockhash)
+            .map_err(|e| ArbitrageError::SecurityViolation(format!("Signing failed: {}", e)))?;
 
         Ok(transaction)
     }
@@ -0,0 +1,64 @@
+use {
+    super::{not_supported, DexInterface},
+    crate::types::common::{ArbitrageError, MarketState},
+    async_trait::async_trait,
+    solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey},
+};
+
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+pub struct JupiterDex;
+
+impl JupiterDex {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DexInterface for JupiterDex {
+    fn name(&self) -> &'static str {
+        "jupiter"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        JUPITER_PROGRAM_ID
+    }
+
+    async fn find_market(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<Option<Pubkey>, ArbitrageError> {
+        // Implement Jupiter market lookup
+        Err(not_supported("Jupiter", "market lookup"))
+    }
+
+    async fn get_market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError> {
+        // Implement Jupiter market state decoding
+        Err(not_supported("Jupiter", "market state"))
+    }
+
+    async fn get_best_price(&self, market: &Pubkey) -> Result<(f64, f64), ArbitrageError> {
+        // Implement Jupiter top of book
+        Err(not_supported("Jupiter", "best price"))
+    }
+
+    async fn get_liquidity(&self, market: &Pubkey) -> Result<u64, ArbitrageError> {
+        // Implement Jupiter liquidity lookup
+        Err(not_supported("Jupiter", "liquidity"))
+    }
+
+    async fn create_swap_instruction(
+        &self,
+        market: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        min_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<Instruction, ArbitrageError> {
+        // Implement Jupiter swap instruction creation
+        Err(not_supported("Jupiter", "swap instruction"))
+    }
+
+    async fn estimate_price_impact(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<f64, ArbitrageError> {
+        // Implement Jupiter price impact estimation
+        Err(not_supported("Jupiter", "price impact"))
+    }
+}
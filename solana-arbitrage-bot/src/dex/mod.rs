@@ -12,17 +12,27 @@ pub use openbook::*;
 
 use {
     crate::types::common::{ArbitrageError, MarketState, TokenPair},
-    solana_sdk::pubkey::Pubkey,
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey},
     async_trait::async_trait,
+    std::{fmt, sync::Arc},
 };
 
 #[async_trait]
-pub trait DexInterface {
+pub trait DexInterface: Send + Sync {
     fn name(&self) -> &'static str;
+    fn program_id(&self) -> Pubkey;
+    async fn find_market(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<Option<Pubkey>, ArbitrageError>;
     async fn get_market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError>;
     async fn get_best_price(&self, market: &Pubkey) -> Result<(f64, f64), ArbitrageError>; // (bid, ask)
     async fn get_liquidity(&self, market: &Pubkey) -> Result<u64, ArbitrageError>;
-    async fn create_swap_instruction(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<Vec<u8>, ArbitrageError>;
+    async fn create_swap_instruction(
+        &self,
+        market: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        min_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<Instruction, ArbitrageError>;
     async fn estimate_price_impact(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<f64, ArbitrageError>;
 }
 
@@ -50,8 +60,10 @@ impl DexRegistry {
         token_pair: &TokenPair,
         amount: u64,
         is_buy: bool,
-    ) -> Result<(&dyn DexInterface, f64), ArbitrageError> {
-        let mut best_price = f64::MAX;
+    ) -> Result<(&dyn DexInterface, Pubkey, f64), ArbitrageError> {
+        // Buys look for the lowest ask, sells for the highest bid
+        let mut best_price = if is_buy { f64::MAX } else { 0.0 };
+        let mut best_market = Pubkey::default();
         let mut best_dex: Option<&dyn DexInterface> = None;
 
         // Check all DEXes for best price
@@ -64,7 +76,7 @@ impl DexRegistry {
         ];
 
         for dex in dexes {
-            if let Ok(market) = self.find_market(dex, token_pair) {
+            if let Ok(Some(market)) = self.find_market(dex, token_pair).await {
                 if let Ok((bid, ask)) = dex.get_best_price(&market).await {
                     let price = if is_buy { ask } else { bid };
                     if (is_buy && price < best_price) || (!is_buy && price > best_price) {
@@ -72,6 +84,7 @@ impl DexRegistry {
                         if let Ok(liquidity) = dex.get_liquidity(&market).await {
                             if liquidity >= amount {
                                 best_price = price;
+                                best_market = market;
                                 best_dex = Some(dex);
                             }
                         }
@@ -81,7 +94,7 @@ impl DexRegistry {
         }
 
         best_dex
-            .map(|dex| (dex, best_price))
+            .map(|dex| (dex, best_market, best_price))
             .ok_or_else(|| ArbitrageError::MarketError("No suitable execution venue found".to_string()))
     }
 
@@ -136,9 +149,8 @@ impl DexRegistry {
         }
     }
 
-    fn find_market(&self, dex: &dyn DexInterface, token_pair: &TokenPair) -> Result<Pubkey, ArbitrageError> {
-        // Implementation would look up the market address for the given token pair on the specific DEX
-        unimplemented!("Market lookup not implemented")
+    async fn find_market(&self, dex: &dyn DexInterface, token_pair: &TokenPair) -> Result<Option<Pubkey>, ArbitrageError> {
+        dex.find_market(&token_pair.base_token.address, &token_pair.quote_token.address).await
     }
 
     fn get_all_markets(&self, token_pair: &TokenPair) -> Result<Vec<MarketInfo>, ArbitrageError> {
@@ -147,10 +159,33 @@ impl DexRegistry {
     }
 }
 
+// Venue calls that are not wired up yet fail, so unwinds and rebalances skip the venue instead of panicking
+fn not_supported(venue: &str, operation: &str) -> ArbitrageError {
+    ArbitrageError::MarketError(format!("{} {} is not implemented", venue, operation))
+}
+
+// Venue whose program an instruction invokes
+pub fn venue_name(program_id: &Pubkey) -> Option<&'static str> {
+    match *program_id {
+        SERUM_PROGRAM_ID => Some("serum"),
+        ORCA_PROGRAM_ID => Some("orca"),
+        RAYDIUM_PROGRAM_ID => Some("raydium"),
+        JUPITER_PROGRAM_ID => Some("jupiter"),
+        OPENBOOK_PROGRAM_ID => Some("openbook"),
+        _ => None,
+    }
+}
+
 #[derive(Clone)]
 pub struct MarketInfo {
     pub address: Pubkey,
-    pub dex: Box<dyn DexInterface>,
+    pub dex: Arc<dyn DexInterface>,
+}
+
+impl fmt::Debug for MarketInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.dex.name(), self.address)
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,64 @@
+use {
+    super::{not_supported, DexInterface},
+    crate::types::common::{ArbitrageError, MarketState},
+    async_trait::async_trait,
+    solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey},
+};
+
+pub const OPENBOOK_PROGRAM_ID: Pubkey = pubkey!("srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX");
+
+pub struct OpenbookDex;
+
+impl OpenbookDex {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DexInterface for OpenbookDex {
+    fn name(&self) -> &'static str {
+        "openbook"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        OPENBOOK_PROGRAM_ID
+    }
+
+    async fn find_market(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<Option<Pubkey>, ArbitrageError> {
+        // Implement OpenBook market lookup
+        Err(not_supported("OpenBook", "market lookup"))
+    }
+
+    async fn get_market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError> {
+        // Implement OpenBook market state decoding
+        Err(not_supported("OpenBook", "market state"))
+    }
+
+    async fn get_best_price(&self, market: &Pubkey) -> Result<(f64, f64), ArbitrageError> {
+        // Implement OpenBook top of book
+        Err(not_supported("OpenBook", "best price"))
+    }
+
+    async fn get_liquidity(&self, market: &Pubkey) -> Result<u64, ArbitrageError> {
+        // Implement OpenBook liquidity lookup
+        Err(not_supported("OpenBook", "liquidity"))
+    }
+
+    async fn create_swap_instruction(
+        &self,
+        market: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        min_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<Instruction, ArbitrageError> {
+        // Implement OpenBook swap instruction creation
+        Err(not_supported("OpenBook", "swap instruction"))
+    }
+
+    async fn estimate_price_impact(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<f64, ArbitrageError> {
+        // Implement OpenBook price impact estimation
+        Err(not_supported("OpenBook", "price impact"))
+    }
+}
@@ -0,0 +1,64 @@
+use {
+    super::{not_supported, DexInterface},
+    crate::types::common::{ArbitrageError, MarketState},
+    async_trait::async_trait,
+    solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey},
+};
+
+pub const ORCA_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+pub struct OrcaDex;
+
+impl OrcaDex {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DexInterface for OrcaDex {
+    fn name(&self) -> &'static str {
+        "orca"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        ORCA_PROGRAM_ID
+    }
+
+    async fn find_market(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<Option<Pubkey>, ArbitrageError> {
+        // Implement Orca market lookup
+        Err(not_supported("Orca", "market lookup"))
+    }
+
+    async fn get_market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError> {
+        // Implement Orca market state decoding
+        Err(not_supported("Orca", "market state"))
+    }
+
+    async fn get_best_price(&self, market: &Pubkey) -> Result<(f64, f64), ArbitrageError> {
+        // Implement Orca top of book
+        Err(not_supported("Orca", "best price"))
+    }
+
+    async fn get_liquidity(&self, market: &Pubkey) -> Result<u64, ArbitrageError> {
+        // Implement Orca liquidity lookup
+        Err(not_supported("Orca", "liquidity"))
+    }
+
+    async fn create_swap_instruction(
+        &self,
+        market: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        min_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<Instruction, ArbitrageError> {
+        // Implement Orca swap instruction creation
+        Err(not_supported("Orca", "swap instruction"))
+    }
+
+    async fn estimate_price_impact(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<f64, ArbitrageError> {
+        // Implement Orca price impact estimation
+        Err(not_supported("Orca", "price impact"))
+    }
+}
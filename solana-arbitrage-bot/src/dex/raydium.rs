@@ -0,0 +1,64 @@
+use {
+    super::{not_supported, DexInterface},
+    crate::types::common::{ArbitrageError, MarketState},
+    async_trait::async_trait,
+    solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey},
+};
+
+pub const RAYDIUM_PROGRAM_ID: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+
+pub struct RaydiumDex;
+
+impl RaydiumDex {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DexInterface for RaydiumDex {
+    fn name(&self) -> &'static str {
+        "raydium"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        RAYDIUM_PROGRAM_ID
+    }
+
+    async fn find_market(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<Option<Pubkey>, ArbitrageError> {
+        // Implement Raydium market lookup
+        Err(not_supported("Raydium", "market lookup"))
+    }
+
+    async fn get_market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError> {
+        // Implement Raydium market state decoding
+        Err(not_supported("Raydium", "market state"))
+    }
+
+    async fn get_best_price(&self, market: &Pubkey) -> Result<(f64, f64), ArbitrageError> {
+        // Implement Raydium top of book
+        Err(not_supported("Raydium", "best price"))
+    }
+
+    async fn get_liquidity(&self, market: &Pubkey) -> Result<u64, ArbitrageError> {
+        // Implement Raydium liquidity lookup
+        Err(not_supported("Raydium", "liquidity"))
+    }
+
+    async fn create_swap_instruction(
+        &self,
+        market: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        min_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<Instruction, ArbitrageError> {
+        // Implement Raydium swap instruction creation
+        Err(not_supported("Raydium", "swap instruction"))
+    }
+
+    async fn estimate_price_impact(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<f64, ArbitrageError> {
+        // Implement Raydium price impact estimation
+        Err(not_supported("Raydium", "price impact"))
+    }
+}
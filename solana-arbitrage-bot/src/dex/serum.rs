@@ -0,0 +1,64 @@
+use {
+    super::{not_supported, DexInterface},
+    crate::types::common::{ArbitrageError, MarketState},
+    async_trait::async_trait,
+    solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey},
+};
+
+pub const SERUM_PROGRAM_ID: Pubkey = pubkey!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+
+pub struct SerumDex;
+
+impl SerumDex {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DexInterface for SerumDex {
+    fn name(&self) -> &'static str {
+        "serum"
+    }
+
+    fn program_id(&self) -> Pubkey {
+        SERUM_PROGRAM_ID
+    }
+
+    async fn find_market(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<Option<Pubkey>, ArbitrageError> {
+        // Implement Serum market lookup
+        Err(not_supported("Serum", "market lookup"))
+    }
+
+    async fn get_market_state(&self, market: &Pubkey) -> Result<MarketState, ArbitrageError> {
+        // Implement Serum market state decoding
+        Err(not_supported("Serum", "market state"))
+    }
+
+    async fn get_best_price(&self, market: &Pubkey) -> Result<(f64, f64), ArbitrageError> {
+        // Implement Serum top of book
+        Err(not_supported("Serum", "best price"))
+    }
+
+    async fn get_liquidity(&self, market: &Pubkey) -> Result<u64, ArbitrageError> {
+        // Implement Serum liquidity lookup
+        Err(not_supported("Serum", "liquidity"))
+    }
+
+    async fn create_swap_instruction(
+        &self,
+        market: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        min_amount_out: u64,
+        is_buy: bool,
+    ) -> Result<Instruction, ArbitrageError> {
+        // Implement Serum swap instruction creation
+        Err(not_supported("Serum", "swap instruction"))
+    }
+
+    async fn estimate_price_impact(&self, market: &Pubkey, amount: u64, is_buy: bool) -> Result<f64, ArbitrageError> {
+        // Implement Serum price impact estimation
+        Err(not_supported("Serum", "price impact"))
+    }
+}
@@ -0,0 +1,404 @@
+mod rebalancer;
+
+pub use rebalancer::*;
+
+use {
+    crate::{
+        config::{Settings, SettingsHandle},
+        dex::DexRegistry,
+        monitoring::{Alert, AlertSender, AlertSeverity},
+        types::common::{ArbitrageError, Token, TokenPair},
+        wallet::ExecutionPool,
+    },
+    solana_sdk::{pubkey::Pubkey, signature::Signer},
+    std::{
+        collections::HashMap,
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+    tokio::sync::watch,
+};
+
+// A non-base balance above dust that no trade has cleared yet
+#[derive(Debug, Clone)]
+pub struct OpenPosition {
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub opened_at: Instant,
+}
+
+pub struct InventoryTracker {
+    settings: SettingsHandle,
+    wallets: Arc<ExecutionPool>,
+    dex: Arc<DexRegistry>,
+    alerts: AlertSender,
+    positions: Mutex<HashMap<(Pubkey, Pubkey), OpenPosition>>,
+}
+
+impl InventoryTracker {
+    pub fn new(
+        settings: SettingsHandle,
+        wallets: Arc<ExecutionPool>,
+        dex: Arc<DexRegistry>,
+        alerts: AlertSender,
+    ) -> Self {
+        Self {
+            settings,
+            wallets,
+            dex,
+            alerts,
+            positions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn positions(&self) -> Vec<OpenPosition> {
+        self.positions.lock().unwrap().values().cloned().collect()
+    }
+
+    // Returns on shutdown; an unwind already sent is waited for, but no new one starts
+    pub async fn run(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) {
+        let scan_interval = self.settings.load().trading.inventory.scan_interval_ms;
+        let mut interval = tokio::time::interval(Duration::from_millis(scan_interval));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => {}
+            }
+            if *shutdown.borrow() {
+                return;
+            }
+
+            // Balances come from the blocking RPC client, so the scan runs on the blocking pool
+            let tracker = self.clone();
+            match tokio::task::spawn_blocking(move || tracker.scan()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    log::warn!("Inventory scan failed: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    log::error!("Inventory scan task failed: {}", e);
+                    continue;
+                }
+            }
+
+            for position in self.expired_positions() {
+                if *shutdown.borrow() {
+                    return;
+                }
+                if let Err(e) = self.unwind(&position, "Position timeout").await {
+                    log::error!("Failed to unwind {} {} in {}: {}", position.amount, position.mint, position.wallet, e);
+                    self.alerts.send(Alert::new(
+                        AlertSeverity::Warning,
+                        "inventory",
+                        format!("Stranded {} of {} in {}: {}", position.amount, position.mint, position.wallet, e),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Refresh open positions from on-chain balances
+    pub fn scan(&self) -> Result<(), ArbitrageError> {
+        let settings = self.settings.load();
+        let mints = tracked_mints(&settings)?;
+
+        let mut balances = Vec::new();
+        for wallet in self.wallets.wallets() {
+            let wallet = wallet.pubkey();
+            for mint in &mints {
+                balances.push((wallet, *mint, self.wallets.token_balance(&wallet, mint)?));
+            }
+        }
+
+        self.record_balances(balances, settings.trading.inventory.dust_amount, Instant::now());
+        Ok(())
+    }
+
+    // `balances` are `(wallet, mint, amount)` for every tracked mint in every wallet
+    fn record_balances(&self, balances: Vec<(Pubkey, Pubkey, u64)>, dust_amount: u64, now: Instant) {
+        let mut positions = self.positions.lock().unwrap();
+        for (wallet, mint, amount) in balances {
+            if amount <= dust_amount {
+                positions.remove(&(wallet, mint));
+                continue;
+            }
+
+            // The clock starts when the balance first appears, not each time it changes
+            positions
+                .entry((wallet, mint))
+                .and_modify(|position| position.amount = amount)
+                .or_insert_with(|| {
+                    log::info!("Open position: {} of {} in {}", amount, mint, wallet);
+                    OpenPosition {
+                        wallet,
+                        mint,
+                        amount,
+                        opened_at: now,
+                    }
+                });
+        }
+    }
+
+    fn expired_positions(&self) -> Vec<OpenPosition> {
+        let timeout = Duration::from_millis(self.settings.load().trading.risk.position_timeout);
+
+        self.positions
+            .lock()
+            .unwrap()
+            .values()
+            // A wallet in the middle of a trade is still working its position
+            .filter(|position| position.opened_at.elapsed() >= timeout && !self.wallets.is_in_flight(&position.wallet))
+            .cloned()
+            .collect()
+    }
+
+    // Sell every non-base balance above dust, target tokens included, back into the base mint.
+    // Wallets still confirming a trade are left alone. Returns how many balances were sold.
+    pub async fn flatten(&self) -> Result<usize, ArbitrageError> {
+        let settings = self.settings.load();
+        let inventory = &settings.trading.inventory;
+        let base_mint = parse_mint(&inventory.base_mint)?;
+        let mints: Vec<Pubkey> = settings
+            .trading
+            .markets
+            .whitelisted_tokens
+            .iter()
+            .filter_map(|mint| Pubkey::from_str(mint).ok())
+            .filter(|mint| *mint != base_mint)
+            .collect();
+
+        let mut flattened = 0;
+        for wallet in self.wallets.wallets() {
+            let wallet = wallet.pubkey();
+            if self.wallets.is_in_flight(&wallet) {
+                log::warn!("Not flattening {}: a trade is still in flight", wallet);
+                continue;
+            }
+
+            for mint in &mints {
+                let mint = *mint;
+                let amount = self.wallets.blocking(move |pool| pool.token_balance(&wallet, &mint)).await?;
+                if amount <= inventory.dust_amount {
+                    continue;
+                }
+
+                let position = OpenPosition {
+                    wallet,
+                    mint,
+                    amount,
+                    opened_at: Instant::now(),
+                };
+                match self.unwind(&position, "Flatten").await {
+                    Ok(true) => flattened += 1,
+                    Ok(false) => log::warn!("Not flattening {}: a trade started on it", wallet),
+                    Err(e) => log::error!("Failed to flatten {} of {} in {}: {}", amount, mint, wallet, e),
+                }
+            }
+        }
+
+        Ok(flattened)
+    }
+
+    // Returns false when the wallet is busy; the lease keeps trades off it until the swap has landed
+    async fn unwind(&self, position: &OpenPosition, reason: &str) -> Result<bool, ArbitrageError> {
+        let settings = self.settings.load();
+        let inventory = &settings.trading.inventory;
+        let base_mint = parse_mint(&inventory.base_mint)?;
+
+        let lease = match self.wallets.lease_wallet(&position.wallet) {
+            Some(lease) => lease,
+            None => {
+                log::debug!("Not unwinding {} in {}: the wallet is busy", position.mint, position.wallet);
+                return Ok(false);
+            }
+        };
+        let signer = lease.signer();
+        let pair = TokenPair {
+            base_token: token(position.mint),
+            quote_token: token(base_mint),
+        };
+
+        let (dex, market, price) = self.dex.get_best_execution_venue(&pair, position.amount, false).await?;
+        let impact = dex.estimate_price_impact(&market, position.amount, false).await?;
+        check_unwind_impact(dex.name(), impact, inventory.max_unwind_slippage)?;
+
+        let min_amount_out = (position.amount as f64 * price * (1.0 - inventory.max_unwind_slippage)) as u64;
+        let instruction = dex
+            .create_swap_instruction(&market, &position.wallet, position.amount, min_amount_out, false)
+            .await?;
+        let signature = self
+            .wallets
+            .blocking(move |pool| pool.send_instructions(&signer, &[instruction]))
+            .await?;
+
+        self.positions.lock().unwrap().remove(&(position.wallet, position.mint));
+
+        log::info!(
+            "Unwound {} of {} in {} via {} (min out {}): {}",
+            position.amount,
+            position.mint,
+            position.wallet,
+            dex.name(),
+            min_amount_out,
+            signature
+        );
+        self.alerts.send(Alert::new(
+            AlertSeverity::Warning,
+            "inventory",
+            format!(
+                "{}: unwound {} of {} in {} via {}",
+                reason,
+                position.amount,
+                position.mint,
+                position.wallet,
+                dex.name()
+            ),
+        ));
+
+        Ok(true)
+    }
+}
+
+// Tokens the tracker unwinds: whitelisted and not the base mint. Tokens with a working balance target belong to
+// the rebalancer
+fn tracked_mints(settings: &Settings) -> Result<Vec<Pubkey>, ArbitrageError> {
+    let inventory = &settings.trading.inventory;
+    let base_mint = parse_mint(&inventory.base_mint)?;
+
+    Ok(settings
+        .trading
+        .markets
+        .whitelisted_tokens
+        .iter()
+        .filter_map(|mint| Pubkey::from_str(mint).ok())
+        .filter(|mint| *mint != base_mint)
+        .filter(|mint| !inventory.targets.iter().any(|target| target.mint == mint.to_string()))
+        .collect())
+}
+
+fn check_unwind_impact(venue: &str, impact: f64, max_unwind_slippage: f64) -> Result<(), ArbitrageError> {
+    if impact > max_unwind_slippage {
+        return Err(ArbitrageError::MarketError(format!(
+            "best venue {} would move the price {:.2}%, cap is {:.2}%",
+            venue,
+            impact * 100.0,
+            max_unwind_slippage * 100.0
+        )));
+    }
+    Ok(())
+}
+
+fn parse_mint(mint: &str) -> Result<Pubkey, ArbitrageError> {
+    Pubkey::from_str(mint).map_err(|e| ArbitrageError::ConfigError(format!("Invalid mint {}: {}", mint, e)))
+}
+
+fn token(address: Pubkey) -> Token {
+    Token {
+        address,
+        symbol: String::new(),
+        decimals: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{config::TokenTarget, journal::Journal, monitoring::alert_channel, signer::SharedSigner},
+        solana_client::rpc_client::RpcClient,
+        solana_sdk::signature::Keypair,
+        std::path::Path,
+    };
+
+    fn tracker(settings: Settings, wallets: usize) -> InventoryTracker {
+        let settings = SettingsHandle::new(settings);
+        let rpc_client = Arc::new(RpcClient::new("http://127.0.0.1:1".to_string()));
+        let journal = Arc::new(Journal::open(Path::new(":memory:")).unwrap());
+        let signers: Vec<SharedSigner> = (0..wallets).map(|_| Arc::new(Keypair::new()) as SharedSigner).collect();
+        let pool = Arc::new(ExecutionPool::new(settings.clone(), rpc_client, journal, signers));
+        let (alerts, _alert_receiver) = alert_channel();
+        InventoryTracker::new(settings, pool, Arc::new(DexRegistry::new()), alerts)
+    }
+
+    fn position(tracker: &InventoryTracker, wallet: &Pubkey, mint: &Pubkey) -> Option<OpenPosition> {
+        tracker.positions.lock().unwrap().get(&(*wallet, *mint)).cloned()
+    }
+
+    #[test]
+    fn test_balances_at_or_below_dust_are_not_positions() {
+        let tracker = tracker(Settings::default(), 1);
+        let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        tracker.record_balances(vec![(wallet, mint, 1000)], 1000, Instant::now());
+        assert!(position(&tracker, &wallet, &mint).is_none());
+
+        tracker.record_balances(vec![(wallet, mint, 1001)], 1000, Instant::now());
+        assert!(position(&tracker, &wallet, &mint).is_some());
+
+        // Falling back to dust closes the position
+        tracker.record_balances(vec![(wallet, mint, 10)], 1000, Instant::now());
+        assert!(position(&tracker, &wallet, &mint).is_none());
+    }
+
+    #[test]
+    fn test_position_clock_does_not_reset_when_the_amount_changes() {
+        let tracker = tracker(Settings::default(), 1);
+        let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let opened_at = Instant::now();
+
+        tracker.record_balances(vec![(wallet, mint, 5_000)], 1000, opened_at);
+        tracker.record_balances(vec![(wallet, mint, 7_000)], 1000, opened_at + Duration::from_secs(10));
+
+        let position = position(&tracker, &wallet, &mint).unwrap();
+        assert_eq!(position.amount, 7_000);
+        assert_eq!(position.opened_at, opened_at);
+    }
+
+    #[test]
+    fn test_expiry_skips_wallets_with_a_trade_in_flight() {
+        let mut settings = Settings::default();
+        settings.trading.risk.position_timeout = 0;
+        let tracker = tracker(settings, 2);
+        let busy = tracker.wallets.wallets()[0].pubkey();
+        let idle = tracker.wallets.wallets()[1].pubkey();
+        let mint = Pubkey::new_unique();
+        tracker.record_balances(vec![(busy, mint, 5_000), (idle, mint, 5_000)], 1000, Instant::now());
+
+        let lease = tracker.wallets.lease_wallet(&busy).unwrap();
+        let expired = tracker.expired_positions();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].wallet, idle);
+
+        drop(lease);
+        assert_eq!(tracker.expired_positions().len(), 2);
+    }
+
+    #[test]
+    fn test_target_tokens_and_base_mint_are_not_tracked() {
+        let (held, targeted) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut settings = Settings::default();
+        settings.trading.markets.whitelisted_tokens = vec![
+            held.to_string(),
+            targeted.to_string(),
+            settings.trading.inventory.base_mint.clone(),
+        ];
+        settings.trading.inventory.targets = vec![TokenTarget {
+            mint: targeted.to_string(),
+            target: 1_000_000,
+            min: 500_000,
+            max: 2_000_000,
+        }];
+
+        assert_eq!(tracked_mints(&settings).unwrap(), vec![held]);
+    }
+
+    #[test]
+    fn test_unwind_refuses_impact_above_the_cap() {
+        assert!(check_unwind_impact("orca", 0.02, 0.02).is_ok());
+        assert!(check_unwind_impact("orca", 0.021, 0.02).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use {
+    super::{parse_mint, token},
+    crate::{
+        config::{SettingsHandle, TokenTarget},
+        dex::DexRegistry,
+        journal::{Journal, RebalanceRecord},
+        signer::SharedSigner,
+        types::common::{ArbitrageError, TokenPair},
+        wallet::ExecutionPool,
+    },
+    solana_sdk::{pubkey::Pubkey, signature::Signer},
+    std::{
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::watch,
+};
+
+// Base fee of the single-signature swap transaction
+const SIGNATURE_FEE_LAMPORTS: u64 = 5000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RebalanceCosts {
+    pub swaps: u64,
+    pub fees: u64,
+    // Paid versus the mid price, in base units
+    pub spread: u64,
+}
+
+impl RebalanceCosts {
+    pub fn total(&self) -> u64 {
+        self.fees + self.spread
+    }
+}
+
+// Swap needed to bring one balance back inside its band, if any
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjustment {
+    Buy(u64),
+    Sell(u64),
+}
+
+pub fn plan_adjustment(balance: u64, target: &TokenTarget) -> Option<Adjustment> {
+    if balance < target.min {
+        Some(Adjustment::Buy(target.target - balance))
+    } else if balance > target.max {
+        Some(Adjustment::Sell(balance - target.target))
+    } else {
+        None
+    }
+}
+
+// Keeps working balances of route tokens in every execution wallet so trades do not need flash loans
+pub struct InventoryRebalancer {
+    settings: SettingsHandle,
+    wallets: Arc<ExecutionPool>,
+    dex: Arc<DexRegistry>,
+    journal: Arc<Journal>,
+    costs: Mutex<RebalanceCosts>,
+}
+
+impl InventoryRebalancer {
+    pub fn new(
+        settings: SettingsHandle,
+        wallets: Arc<ExecutionPool>,
+        dex: Arc<DexRegistry>,
+        journal: Arc<Journal>,
+    ) -> Self {
+        Self {
+            settings,
+            wallets,
+            dex,
+            journal,
+            costs: Mutex::new(RebalanceCosts::default()),
+        }
+    }
+
+    pub fn costs(&self) -> RebalanceCosts {
+        *self.costs.lock().unwrap()
+    }
+
+    // `stopped` follows the engine's emergency stop; no rebalancing happens while it is set
+    // Stands down while `stopped` and returns on shutdown
+    pub async fn run(self: Arc<Self>, stopped: watch::Receiver<bool>, mut shutdown: watch::Receiver<bool>) {
+        let interval_ms = self.settings.load().trading.inventory.rebalance_interval_ms;
+        let mut interval = tokio::time::interval(Duration::from_millis(interval_ms));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => {}
+            }
+            if *shutdown.borrow() {
+                return;
+            }
+
+            let settings = self.settings.load();
+            if *stopped.borrow() || settings.trading.inventory.targets.is_empty() {
+                continue;
+            }
+
+            // Only trade against our own inventory when arbitrage has been quiet for a while
+            let quiet_period = Duration::from_secs(settings.trading.inventory.quiet_period_secs);
+            if self.wallets.idle_for() < quiet_period {
+                continue;
+            }
+
+            if let Err(e) = self.rebalance().await {
+                log::error!("Inventory rebalance failed: {}", e);
+            }
+        }
+    }
+
+    pub async fn rebalance(&self) -> Result<(), ArbitrageError> {
+        let settings = self.settings.load();
+        let inventory = &settings.trading.inventory;
+        let base_mint = parse_mint(&inventory.base_mint)?;
+
+        for signer in self.wallets.wallets() {
+            let wallet = signer.pubkey();
+
+            // A trade may have started since the quiet check; the lease keeps new ones off until the swaps land
+            let lease = match self.wallets.lease_wallet(&wallet) {
+                Some(lease) => lease,
+                None => continue,
+            };
+            let signer = lease.signer();
+
+            for target in &inventory.targets {
+                let mint = parse_mint(&target.mint)?;
+                let balance = self.wallets.blocking(move |pool| pool.token_balance(&wallet, &mint)).await?;
+                let adjustment = match plan_adjustment(balance, target) {
+                    Some(adjustment) => adjustment,
+                    None => continue,
+                };
+
+                let max_slippage = inventory.max_rebalance_slippage;
+                if let Err(e) = self.swap(&signer, mint, base_mint, adjustment, max_slippage).await {
+                    log::warn!("Could not rebalance {} in {}: {}", mint, wallet, e);
+                }
+            }
+        }
+
+        let costs = self.costs();
+        log::info!(
+            "Rebalancing costs so far: {} swaps, {} fees + {} spread = {}",
+            costs.swaps,
+            costs.fees,
+            costs.spread,
+            costs.total()
+        );
+
+        Ok(())
+    }
+
+    async fn swap(
+        &self,
+        signer: &SharedSigner,
+        mint: Pubkey,
+        base_mint: Pubkey,
+        adjustment: Adjustment,
+        max_slippage: f64,
+    ) -> Result<(), ArbitrageError> {
+        let wallet = signer.pubkey();
+        let pair = TokenPair {
+            base_token: token(mint),
+            quote_token: token(base_mint),
+        };
+        let (is_buy, quantity) = match adjustment {
+            Adjustment::Buy(quantity) => (true, quantity),
+            Adjustment::Sell(quantity) => (false, quantity),
+        };
+
+        // Cheapest venue for this direction: lowest ask to buy, highest bid to sell
+        let (dex, market, price) = self.dex.get_best_execution_venue(&pair, quantity, is_buy).await?;
+        let impact = dex.estimate_price_impact(&market, quantity, is_buy).await?;
+        if impact > max_slippage {
+            return Err(ArbitrageError::MarketError(format!(
+                "{} would move the price {:.2}%, cap is {:.2}%",
+                dex.name(),
+                impact * 100.0,
+                max_slippage * 100.0
+            )));
+        }
+
+        // The swap amount is always the input side: base to buy, token to sell
+        let (amount_in, min_amount_out) = if is_buy {
+            ((quantity as f64 * price) as u64, (quantity as f64 * (1.0 - max_slippage)) as u64)
+        } else {
+            (quantity, (quantity as f64 * price * (1.0 - max_slippage)) as u64)
+        };
+
+        let (bid, ask) = dex.get_best_price(&market).await?;
+        let mid = (bid + ask) / 2.0;
+        let spread = (quantity as f64 * (price - mid).abs()) as u64;
+
+        let instruction = dex
+            .create_swap_instruction(&market, &wallet, amount_in, min_amount_out, is_buy)
+            .await?;
+        let signer = signer.clone();
+        let signature = self
+            .wallets
+            .blocking(move |pool| pool.send_instructions(&signer, &[instruction]))
+            .await?;
+
+        let cost = {
+            let mut costs = self.costs.lock().unwrap();
+            costs.swaps += 1;
+            costs.fees += SIGNATURE_FEE_LAMPORTS;
+            costs.spread += spread;
+            SIGNATURE_FEE_LAMPORTS + spread
+        };
+
+        log::info!(
+            "Rebalanced {} {} {} in {} via {} (cost {}): {}",
+            if is_buy { "bought" } else { "sold" },
+            quantity,
+            mint,
+            wallet,
+            dex.name(),
+            cost,
+            signature
+        );
+
+        let record = RebalanceRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            wallet: wallet.to_string(),
+            mint: mint.to_string(),
+            is_buy,
+            amount_in,
+            min_amount_out,
+            venue: dex.name().to_string(),
+            cost,
+            signature: signature.to_string(),
+        };
+        // SQLite writes block, so they stay off the runtime workers
+        let journal = self.journal.clone();
+        tokio::task::spawn_blocking(move || journal.record_rebalance(&record))
+            .await
+            .map_err(|e| ArbitrageError::TransactionError(format!("Journal task failed: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_adjustment_returns_to_target() {
+        let target = TokenTarget {
+            mint: String::new(),
+            target: 100,
+            min: 80,
+            max: 150,
+        };
+
+        assert_eq!(plan_adjustment(50, &target), Some(Adjustment::Buy(50)));
+        assert_eq!(plan_adjustment(90, &target), None);
+        assert_eq!(plan_adjustment(200, &target), Some(Adjustment::Sell(100)));
+    }
+}
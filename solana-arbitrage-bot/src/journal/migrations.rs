@@ -0,0 +1,127 @@
+use {
+    crate::types::common::ArbitrageError,
+    rusqlite::Connection,
+};
+
+// Applied in order; `PRAGMA user_version` records how many have run. Never edit a
+// released migration, append a new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: opportunities, executions, transfers and rebalances
+    "CREATE TABLE opportunities (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        slot INTEGER NOT NULL,
+        strategy TEXT NOT NULL,
+        source_market TEXT NOT NULL,
+        target_market TEXT NOT NULL,
+        base_mint TEXT NOT NULL,
+        quote_mint TEXT NOT NULL,
+        route TEXT NOT NULL,
+        required_amount INTEGER NOT NULL,
+        estimated_profit INTEGER NOT NULL,
+        decision TEXT NOT NULL,
+        reason TEXT
+    );
+    CREATE INDEX opportunities_by_time ON opportunities (timestamp);
+
+    CREATE TABLE executions (
+        id INTEGER PRIMARY KEY,
+        opportunity_id INTEGER REFERENCES opportunities (id),
+        timestamp INTEGER NOT NULL,
+        strategy TEXT NOT NULL,
+        token TEXT NOT NULL,
+        route_shape TEXT NOT NULL,
+        venues TEXT NOT NULL,
+        wallet TEXT NOT NULL,
+        notional INTEGER NOT NULL,
+        signature TEXT,
+        slot INTEGER,
+        status TEXT NOT NULL,
+        error TEXT,
+        estimated_profit INTEGER NOT NULL,
+        realized_profit INTEGER,
+        gross_profit INTEGER,
+        fee INTEGER NOT NULL,
+        tip INTEGER NOT NULL,
+        rent INTEGER NOT NULL,
+        build_ms INTEGER NOT NULL,
+        simulate_ms INTEGER NOT NULL,
+        send_ms INTEGER NOT NULL,
+        confirm_ms INTEGER NOT NULL,
+        total_ms INTEGER NOT NULL
+    );
+    CREATE INDEX executions_by_time ON executions (timestamp);
+
+    CREATE TABLE transfers (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        source TEXT NOT NULL,
+        destination TEXT NOT NULL,
+        mint TEXT,
+        amount INTEGER NOT NULL,
+        signature TEXT NOT NULL
+    );
+
+    CREATE TABLE rebalances (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        wallet TEXT NOT NULL,
+        mint TEXT NOT NULL,
+        is_buy INTEGER NOT NULL,
+        amount_in INTEGER NOT NULL,
+        min_amount_out INTEGER NOT NULL,
+        venue TEXT NOT NULL,
+        cost INTEGER NOT NULL,
+        signature TEXT NOT NULL
+    );",
+    // 2: the fixed rejection label alongside the free-text reason, so reports can group on it
+    "ALTER TABLE opportunities ADD COLUMN label TEXT;",
+    // 3: switches of the active hot wallet
+    "CREATE TABLE wallet_rotations (
+        id INTEGER PRIMARY KEY,
+        timestamp INTEGER NOT NULL,
+        retired TEXT NOT NULL,
+        active TEXT NOT NULL
+    );",
+];
+
+pub fn migrate(conn: &mut Connection) -> Result<(), ArbitrageError> {
+    let failed = |e: rusqlite::Error| ArbitrageError::ConfigError(format!("Journal migration failed: {}", e));
+
+    let applied: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(failed)?;
+    if applied > MIGRATIONS.len() {
+        return Err(ArbitrageError::ConfigError(format!(
+            "Journal schema version {} is newer than this build supports ({})",
+            applied,
+            MIGRATIONS.len()
+        )));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = conn.transaction().map_err(failed)?;
+        tx.execute_batch(migration).map_err(failed)?;
+        tx.pragma_update(None, "user_version", index + 1).map_err(failed)?;
+        tx.commit().map_err(failed)?;
+        log::info!("Applied journal migration {}", index + 1);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        migrate(&mut conn).unwrap();
+
+        let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+    }
+}
@@ -0,0 +1,593 @@
+mod migrations;
+mod report;
+
+pub use report::*;
+
+use {
+    crate::types::common::{ArbitrageError, ArbitrageOpportunity, TradeStep, TradeSide},
+    rusqlite::{params, Connection, Row},
+    serde::{Deserialize, Serialize},
+    solana_sdk::pubkey::Pubkey,
+    std::{fmt, path::Path, str::FromStr, sync::Mutex, time::Duration},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferKind {
+    // Fee SOL moved into a wallet that is about to become active
+    TopUp,
+    // Profits moved from a retired hot wallet to the cold address
+    Sweep,
+    // Inventory moved between execution wallets
+    Rebalance,
+}
+
+impl TransferKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TransferKind::TopUp => "top_up",
+            TransferKind::Sweep => "sweep",
+            TransferKind::Rebalance => "rebalance",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferRecord {
+    pub timestamp: i64,
+    pub kind: TransferKind,
+    pub from: String,
+    pub to: String,
+    // Token mint for SPL transfers; `None` means `amount` is in lamports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+    pub amount: u64,
+    pub signature: String,
+}
+
+// The active hot wallet changed from `retired` to `active`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationRecord {
+    pub timestamp: i64,
+    pub retired: String,
+    pub active: String,
+}
+
+// Inventory swap made to bring a wallet back inside its target band; its cost is
+// kept apart from arbitrage PnL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceRecord {
+    pub timestamp: i64,
+    pub wallet: String,
+    pub mint: String,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub venue: String,
+    pub cost: u64,
+    pub signature: String,
+}
+
+// What the engine did with an opportunity it found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Skipped,
+    // Simulated but not sent
+    Simulated,
+    Sent,
+}
+
+impl Decision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Decision::Skipped => "skipped",
+            Decision::Simulated => "simulated",
+            Decision::Sent => "sent",
+        }
+    }
+}
+
+impl FromStr for Decision {
+    type Err = ArbitrageError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "skipped" => Ok(Decision::Skipped),
+            "simulated" => Ok(Decision::Simulated),
+            "sent" => Ok(Decision::Sent),
+            _ => Err(ArbitrageError::ConfigError(format!("Unknown decision in journal: {}", source))),
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpportunityRecord {
+    pub timestamp: i64,
+    pub slot: u64,
+    pub strategy: String,
+    pub source_market: String,
+    pub target_market: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    // Every leg with its size and quoted price
+    pub route: Vec<TradeStep>,
+    pub required_amount: u64,
+    pub estimated_profit: u64,
+    pub decision: Decision,
+    pub reason: Option<String>,
+    // Fixed rejection reason matching the metrics label, e.g. `slots_busy`; `None` on rows from older versions
+    pub label: Option<String>,
+}
+
+impl OpportunityRecord {
+    pub fn new(opportunity: &ArbitrageOpportunity, decision: Decision, reason: Option<String>) -> Self {
+        Self {
+            timestamp: now_unix(),
+            slot: opportunity.slot,
+            strategy: opportunity.strategy.clone(),
+            source_market: opportunity.source_market.to_string(),
+            target_market: opportunity.target_market.to_string(),
+            base_mint: opportunity.token_pair.base_token.address.to_string(),
+            quote_mint: opportunity.token_pair.quote_token.address.to_string(),
+            route: opportunity.route.clone(),
+            required_amount: opportunity.required_amount,
+            estimated_profit: opportunity.estimated_profit,
+            decision,
+            reason,
+            label: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    Landed,
+    // Send failed, or the transaction expired or errored on chain
+    Failed,
+}
+
+impl ExecutionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionStatus::Landed => "landed",
+            ExecutionStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for ExecutionStatus {
+    type Err = ArbitrageError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "landed" => Ok(ExecutionStatus::Landed),
+            "failed" => Ok(ExecutionStatus::Failed),
+            _ => Err(ArbitrageError::ConfigError(format!("Unknown execution status in journal: {}", source))),
+        }
+    }
+}
+
+// Where the time of one send went, in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Latency {
+    pub build_ms: u64,
+    pub simulate_ms: u64,
+    pub send_ms: u64,
+    pub confirm_ms: u64,
+    pub total_ms: u64,
+}
+
+impl Latency {
+    pub fn millis(duration: Duration) -> u64 {
+        duration.as_millis() as u64
+    }
+}
+
+// A transaction that was sent, with its estimate next to what the balance changes show
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub timestamp: i64,
+    pub opportunity_id: Option<i64>,
+    pub strategy: String,
+    pub token: String,
+    pub route_shape: String,
+    pub venues: Vec<String>,
+    pub wallet: String,
+    pub notional: u64,
+    pub signature: Option<String>,
+    pub slot: Option<u64>,
+    pub status: ExecutionStatus,
+    pub error: Option<String>,
+    pub estimated_profit: i64,
+    // `None` when the trade failed or its balance changes could not be read back
+    pub realized_profit: Option<i64>,
+    pub gross_profit: Option<i64>,
+    pub fee: u64,
+    pub tip: u64,
+    pub rent: u64,
+    pub latency: Latency,
+}
+
+// Legs of a route as `buy>sell`, with flash loan legs shown as `loan`
+pub fn route_shape(route: &[TradeStep]) -> String {
+    route
+        .iter()
+        .map(|step| match step.side {
+            _ if step.market == Pubkey::default() => "loan",
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        })
+        .collect::<Vec<_>>()
+        .join(">")
+}
+
+// Embedded SQLite record of everything the bot saw and did, queried by the reporting commands
+pub struct Journal {
+    conn: Mutex<Connection>,
+}
+
+impl Journal {
+    pub fn open(path: &Path) -> Result<Self, ArbitrageError> {
+        let mut conn = Connection::open(path).map_err(|e| {
+            ArbitrageError::ConfigError(format!("Failed to open journal {}: {}", path.display(), e))
+        })?;
+
+        // The bot and the reporting commands may have the journal open at the same time
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))
+            .and_then(|_| conn.busy_timeout(Duration::from_secs(5)))
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to configure journal: {}", e)))?;
+        migrations::migrate(&mut conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record_transfer(&self, record: &TransferRecord) -> Result<(), ArbitrageError> {
+        self.execute(
+            "INSERT INTO transfers (timestamp, kind, source, destination, mint, amount, signature)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                record.timestamp,
+                record.kind.as_str(),
+                record.from,
+                record.to,
+                record.mint,
+                record.amount,
+                record.signature,
+            ],
+        )
+        .map(|_| ())
+    }
+
+    pub fn record_rotation(&self, record: &RotationRecord) -> Result<(), ArbitrageError> {
+        self.execute(
+            "INSERT INTO wallet_rotations (timestamp, retired, active) VALUES (?1, ?2, ?3)",
+            params![record.timestamp, record.retired, record.active],
+        )
+        .map(|_| ())
+    }
+
+    pub fn record_rebalance(&self, record: &RebalanceRecord) -> Result<(), ArbitrageError> {
+        self.execute(
+            "INSERT INTO rebalances (timestamp, wallet, mint, is_buy, amount_in, min_amount_out, venue, cost, signature)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.timestamp,
+                record.wallet,
+                record.mint,
+                record.is_buy,
+                record.amount_in,
+                record.min_amount_out,
+                record.venue,
+                record.cost,
+                record.signature,
+            ],
+        )
+        .map(|_| ())
+    }
+
+    // Returns the row id so executions can point back at their opportunity
+    pub fn record_opportunity(&self, record: &OpportunityRecord) -> Result<i64, ArbitrageError> {
+        self.execute(
+            "INSERT INTO opportunities (timestamp, slot, strategy, source_market, target_market, base_mint,
+                 quote_mint, route, required_amount, estimated_profit, decision, reason, label)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                record.timestamp,
+                record.slot,
+                record.strategy,
+                record.source_market,
+                record.target_market,
+                record.base_mint,
+                record.quote_mint,
+                encode(&record.route)?,
+                record.required_amount,
+                record.estimated_profit,
+                record.decision.as_str(),
+                record.reason,
+                record.label,
+            ],
+        )
+    }
+
+    pub fn record_execution(&self, record: &ExecutionRecord) -> Result<i64, ArbitrageError> {
+        self.execute(
+            "INSERT INTO executions (opportunity_id, timestamp, strategy, token, route_shape, venues, wallet,
+                 notional, signature, slot, status, error, estimated_profit, realized_profit, gross_profit,
+                 fee, tip, rent, build_ms, simulate_ms, send_ms, confirm_ms, total_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
+                 ?20, ?21, ?22, ?23)",
+            params![
+                record.opportunity_id,
+                record.timestamp,
+                record.strategy,
+                record.token,
+                record.route_shape,
+                encode(&record.venues)?,
+                record.wallet,
+                record.notional,
+                record.signature,
+                record.slot,
+                record.status.as_str(),
+                record.error,
+                record.estimated_profit,
+                record.realized_profit,
+                record.gross_profit,
+                record.fee,
+                record.tip,
+                record.rent,
+                record.latency.build_ms,
+                record.latency.simulate_ms,
+                record.latency.send_ms,
+                record.latency.confirm_ms,
+                record.latency.total_ms,
+            ],
+        )
+    }
+
+    // Opportunities found in `[since, until)`, oldest first
+    pub fn opportunities(&self, since: i64, until: i64) -> Result<Vec<OpportunityRecord>, ArbitrageError> {
+        self.query(
+            "SELECT timestamp, slot, strategy, source_market, target_market, base_mint, quote_mint, route,
+                 required_amount, estimated_profit, decision, reason, label
+             FROM opportunities WHERE timestamp >= ?1 AND timestamp < ?2 ORDER BY id",
+            since,
+            until,
+            |row| {
+                Ok(OpportunityRecord {
+                    timestamp: row.get(0)?,
+                    slot: row.get(1)?,
+                    strategy: row.get(2)?,
+                    source_market: row.get(3)?,
+                    target_market: row.get(4)?,
+                    base_mint: row.get(5)?,
+                    quote_mint: row.get(6)?,
+                    route: decode(row, 7)?,
+                    required_amount: row.get(8)?,
+                    estimated_profit: row.get(9)?,
+                    decision: parse(row, 10)?,
+                    reason: row.get(11)?,
+                    label: row.get(12)?,
+                })
+            },
+        )
+    }
+
+    // Executions sent in `[since, until)`, oldest first
+    pub fn executions(&self, since: i64, until: i64) -> Result<Vec<ExecutionRecord>, ArbitrageError> {
+        self.query(
+            "SELECT opportunity_id, timestamp, strategy, token, route_shape, venues, wallet, notional, signature,
+                 slot, status, error, estimated_profit, realized_profit, gross_profit, fee, tip, rent,
+                 build_ms, simulate_ms, send_ms, confirm_ms, total_ms
+             FROM executions WHERE timestamp >= ?1 AND timestamp < ?2 ORDER BY id",
+            since,
+            until,
+            |row| {
+                Ok(ExecutionRecord {
+                    opportunity_id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    strategy: row.get(2)?,
+                    token: row.get(3)?,
+                    route_shape: row.get(4)?,
+                    venues: decode(row, 5)?,
+                    wallet: row.get(6)?,
+                    notional: row.get(7)?,
+                    signature: row.get(8)?,
+                    slot: row.get(9)?,
+                    status: parse(row, 10)?,
+                    error: row.get(11)?,
+                    estimated_profit: row.get(12)?,
+                    realized_profit: row.get(13)?,
+                    gross_profit: row.get(14)?,
+                    fee: row.get(15)?,
+                    tip: row.get(16)?,
+                    rent: row.get(17)?,
+                    latency: Latency {
+                        build_ms: row.get(18)?,
+                        simulate_ms: row.get(19)?,
+                        send_ms: row.get(20)?,
+                        confirm_ms: row.get(21)?,
+                        total_ms: row.get(22)?,
+                    },
+                })
+            },
+        )
+    }
+
+    // Rebalancing swaps made in `[since, until)`, oldest first
+    pub fn rebalances(&self, since: i64, until: i64) -> Result<Vec<RebalanceRecord>, ArbitrageError> {
+        self.query(
+            "SELECT timestamp, wallet, mint, is_buy, amount_in, min_amount_out, venue, cost, signature
+             FROM rebalances WHERE timestamp >= ?1 AND timestamp < ?2 ORDER BY id",
+            since,
+            until,
+            |row| {
+                Ok(RebalanceRecord {
+                    timestamp: row.get(0)?,
+                    wallet: row.get(1)?,
+                    mint: row.get(2)?,
+                    is_buy: row.get(3)?,
+                    amount_in: row.get(4)?,
+                    min_amount_out: row.get(5)?,
+                    venue: row.get(6)?,
+                    cost: row.get(7)?,
+                    signature: row.get(8)?,
+                })
+            },
+        )
+    }
+
+    // Folds the write-ahead log back into the database file, so nothing depends on the WAL after exit
+    pub fn checkpoint(&self) -> Result<(), ArbitrageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| ArbitrageError::TransactionError(format!("Failed to checkpoint journal: {}", e)))
+    }
+
+    fn execute(&self, sql: &str, params: impl rusqlite::Params) -> Result<i64, ArbitrageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(sql, params)
+            .map(|_| conn.last_insert_rowid())
+            .map_err(|e| ArbitrageError::TransactionError(format!("Failed to write journal: {}", e)))
+    }
+
+    fn query<T>(
+        &self,
+        sql: &str,
+        since: i64,
+        until: i64,
+        map: impl FnMut(&Row<'_>) -> rusqlite::Result<T>,
+    ) -> Result<Vec<T>, ArbitrageError> {
+        let failed = |e: rusqlite::Error| ArbitrageError::TransactionError(format!("Journal query failed: {}", e));
+
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare_cached(sql).map_err(failed)?;
+        let rows = statement.query_map(params![since, until], map).map_err(failed)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(failed)
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<String, ArbitrageError> {
+    serde_json::to_string(value)
+        .map_err(|e| ArbitrageError::TransactionError(format!("Failed to encode journal entry: {}", e)))
+}
+
+fn decode<T: serde::de::DeserializeOwned>(row: &Row<'_>, index: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(index)?;
+    serde_json::from_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn parse<T: FromStr<Err = ArbitrageError>>(row: &Row<'_>, index: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(index)?;
+    text.parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::types::common::{Token, TokenPair},
+    };
+
+    fn opportunity() -> ArbitrageOpportunity {
+        let token = Token {
+            address: Pubkey::new_unique(),
+            symbol: String::new(),
+            decimals: 9,
+        };
+        let step = |side| TradeStep {
+            market: Pubkey::new_unique(),
+            side,
+            amount: 1_000,
+            price: 1.5,
+        };
+        ArbitrageOpportunity {
+            source_market: Pubkey::new_unique(),
+            target_market: Pubkey::new_unique(),
+            token_pair: TokenPair {
+                base_token: token.clone(),
+                quote_token: token,
+            },
+            profit_percentage: 0.0,
+            required_amount: 1_000,
+            estimated_profit: 25,
+            route: vec![step(TradeSide::Buy), step(TradeSide::Sell)],
+            timestamp: 0,
+            slot: 42,
+            strategy: "direct".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trips_opportunities_and_executions() {
+        let journal = Journal::open(Path::new(":memory:")).unwrap();
+        let opportunity = opportunity();
+
+        let vetoed = OpportunityRecord::new(&opportunity, Decision::Skipped, Some("risk veto".into()));
+        journal.record_opportunity(&vetoed.with_label("risk_limit")).unwrap();
+        let id = journal
+            .record_opportunity(&OpportunityRecord::new(&opportunity, Decision::Sent, None))
+            .unwrap();
+        journal
+            .record_execution(&ExecutionRecord {
+                timestamp: 100,
+                opportunity_id: Some(id),
+                strategy: opportunity.strategy.clone(),
+                token: opportunity.token_pair.base_token.address.to_string(),
+                route_shape: route_shape(&opportunity.route),
+                venues: vec!["orca".to_string()],
+                wallet: Pubkey::new_unique().to_string(),
+                notional: 1_000,
+                signature: Some("sig".to_string()),
+                slot: Some(43),
+                status: ExecutionStatus::Landed,
+                error: None,
+                estimated_profit: 25,
+                realized_profit: Some(-3),
+                gross_profit: Some(2),
+                fee: 5,
+                tip: 0,
+                rent: 0,
+                latency: Latency { build_ms: 1, simulate_ms: 2, send_ms: 3, confirm_ms: 4, total_ms: 10 },
+            })
+            .unwrap();
+
+        let opportunities = journal.opportunities(0, i64::MAX).unwrap();
+        assert_eq!(opportunities.len(), 2);
+        assert_eq!(opportunities[0].decision, Decision::Skipped);
+        assert_eq!(opportunities[0].reason.as_deref(), Some("risk veto"));
+        assert_eq!(opportunities[0].label.as_deref(), Some("risk_limit"));
+        assert_eq!(opportunities[1].label, None);
+        assert_eq!(opportunities[1].route.len(), 2);
+
+        let executions = journal.executions(0, 200).unwrap();
+        assert_eq!(executions[0].opportunity_id, Some(id));
+        assert_eq!(executions[0].route_shape, "buy>sell");
+        assert_eq!(executions[0].realized_profit, Some(-3));
+        assert_eq!(executions[0].latency.total_ms, 10);
+        assert!(journal.executions(101, 200).unwrap().is_empty());
+    }
+}
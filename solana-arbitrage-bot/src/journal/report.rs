@@ -0,0 +1,255 @@
+use {
+    super::{Decision, ExecutionRecord, ExecutionStatus, OpportunityRecord, RebalanceRecord},
+    serde::Serialize,
+    std::collections::BTreeMap,
+};
+
+// Performance of one strategy, token, DEX or route shape over a report window
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GroupStats {
+    pub key: String,
+    pub sent: u64,
+    pub landed: u64,
+    // Landed with a positive realized profit
+    pub profitable: u64,
+    pub hit_rate: f64,
+    pub estimated_pnl: i64,
+    pub realized_pnl: i64,
+    // Per landed trade with a measured outcome
+    pub avg_estimated: f64,
+    pub avg_realized: f64,
+    pub fees: u64,
+    pub tips: u64,
+    pub notional: u64,
+    // Share of the window's capacity (max position x concurrent trades) that was in use
+    pub capital_utilization: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailureCount {
+    // `skipped`, `simulated` or `failed`
+    pub stage: String,
+    // Fixed label such as `slots_busy` or `confirm_failed`, so free-text details do not split the counts
+    pub reason: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PnlReport {
+    pub since: i64,
+    pub until: i64,
+    pub opportunities: u64,
+    pub total: GroupStats,
+    pub by_strategy: Vec<GroupStats>,
+    pub by_token: Vec<GroupStats>,
+    pub by_dex: Vec<GroupStats>,
+    pub by_route: Vec<GroupStats>,
+    pub failures: Vec<FailureCount>,
+    // Inventory upkeep, kept out of arbitrage PnL
+    pub rebalance_cost: u64,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    stats: GroupStats,
+    measured: u64,
+    measured_estimate: i64,
+    // Notional multiplied by how long it was committed, in base-unit milliseconds
+    deployed: u128,
+}
+
+impl Accumulator {
+    fn add(&mut self, execution: &ExecutionRecord) {
+        let stats = &mut self.stats;
+        stats.sent += 1;
+        stats.fees += execution.fee;
+        stats.tips += execution.tip;
+        stats.notional += execution.notional;
+        self.deployed += execution.notional as u128 * execution.latency.total_ms as u128;
+
+        if execution.status != ExecutionStatus::Landed {
+            return;
+        }
+        stats.landed += 1;
+        stats.estimated_pnl += execution.estimated_profit;
+
+        if let Some(realized) = execution.realized_profit {
+            stats.realized_pnl += realized;
+            self.measured += 1;
+            self.measured_estimate += execution.estimated_profit;
+            if realized > 0 {
+                stats.profitable += 1;
+            }
+        }
+    }
+
+    fn finish(mut self, key: String, capacity: u128) -> GroupStats {
+        let stats = &mut self.stats;
+        stats.key = key;
+        stats.hit_rate = stats.profitable as f64 / stats.sent.max(1) as f64;
+        stats.avg_estimated = self.measured_estimate as f64 / self.measured.max(1) as f64;
+        stats.avg_realized = stats.realized_pnl as f64 / self.measured.max(1) as f64;
+        stats.capital_utilization = self.deployed as f64 / capacity.max(1) as f64;
+        self.stats
+    }
+}
+
+fn group<'a>(
+    executions: &'a [ExecutionRecord],
+    capacity: u128,
+    keys: impl Fn(&'a ExecutionRecord) -> Vec<String>,
+) -> Vec<GroupStats> {
+    let mut groups: BTreeMap<String, Accumulator> = BTreeMap::new();
+    for execution in executions {
+        for key in keys(execution) {
+            groups.entry(key).or_default().add(execution);
+        }
+    }
+
+    let mut stats: Vec<GroupStats> = groups.into_iter().map(|(key, group)| group.finish(key, capacity)).collect();
+    stats.sort_by(|a, b| b.realized_pnl.cmp(&a.realized_pnl));
+    stats
+}
+
+// `capacity` is how much could have been deployed at once, in base units
+pub fn build_report(
+    since: i64,
+    until: i64,
+    opportunities: &[OpportunityRecord],
+    executions: &[ExecutionRecord],
+    rebalances: &[RebalanceRecord],
+    capacity: u64,
+) -> PnlReport {
+    let capacity = capacity as u128 * (until - since).max(0) as u128 * 1000;
+
+    let mut total = Accumulator::default();
+    for execution in executions {
+        total.add(execution);
+    }
+
+    let mut failures: BTreeMap<(String, String), u64> = BTreeMap::new();
+    for opportunity in opportunities.iter().filter(|opportunity| opportunity.decision != Decision::Sent) {
+        // Rows written before labels were journaled only have the free-text reason
+        let label = opportunity
+            .label
+            .clone()
+            .or_else(|| opportunity.reason.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        *failures.entry((opportunity.decision.to_string(), label)).or_default() += 1;
+    }
+    for execution in executions.iter().filter(|execution| execution.status == ExecutionStatus::Failed) {
+        // Only a sent transaction has a signature
+        let label = if execution.signature.is_some() { "confirm_failed" } else { "send_failed" };
+        *failures.entry(("failed".to_string(), label.to_string())).or_default() += 1;
+    }
+    let mut failures: Vec<FailureCount> = failures
+        .into_iter()
+        .map(|((stage, reason), count)| FailureCount { stage, reason, count })
+        .collect();
+    failures.sort_by(|a, b| b.count.cmp(&a.count));
+
+    PnlReport {
+        since,
+        until,
+        opportunities: opportunities.len() as u64,
+        total: total.finish("total".to_string(), capacity),
+        by_strategy: group(executions, capacity, |execution| vec![execution.strategy.clone()]),
+        by_token: group(executions, capacity, |execution| vec![execution.token.clone()]),
+        // A route through two venues counts towards both
+        by_dex: group(executions, capacity, |execution| execution.venues.clone()),
+        by_route: group(executions, capacity, |execution| vec![execution.route_shape.clone()]),
+        failures,
+        rebalance_cost: rebalances.iter().map(|rebalance| rebalance.cost).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::journal::Latency};
+
+    fn execution(strategy: &str, venues: &[&str], status: ExecutionStatus, realized: Option<i64>) -> ExecutionRecord {
+        ExecutionRecord {
+            timestamp: 0,
+            opportunity_id: None,
+            strategy: strategy.to_string(),
+            token: "token".to_string(),
+            route_shape: "buy>sell".to_string(),
+            venues: venues.iter().map(|venue| venue.to_string()).collect(),
+            wallet: String::new(),
+            notional: 1_000,
+            signature: None,
+            slot: None,
+            status,
+            error: (status == ExecutionStatus::Failed).then(|| "blockhash expired".to_string()),
+            estimated_profit: 50,
+            realized_profit: realized,
+            gross_profit: realized,
+            fee: 5,
+            tip: 1,
+            rent: 0,
+            latency: Latency { total_ms: 500, ..Latency::default() },
+        }
+    }
+
+    #[test]
+    fn test_report_groups_pnl_and_failures() {
+        let executions = vec![
+            execution("jit", &["orca", "raydium"], ExecutionStatus::Landed, Some(40)),
+            execution("jit", &["orca"], ExecutionStatus::Landed, Some(-10)),
+            execution("direct", &["raydium"], ExecutionStatus::Failed, None),
+        ];
+
+        let opportunity = |reason: &str, label: Option<&str>| OpportunityRecord {
+            timestamp: 0,
+            slot: 0,
+            strategy: "jit".to_string(),
+            source_market: String::new(),
+            target_market: String::new(),
+            base_mint: String::new(),
+            quote_mint: String::new(),
+            route: vec![],
+            required_amount: 0,
+            estimated_profit: 0,
+            decision: Decision::Skipped,
+            reason: Some(reason.to_string()),
+            label: label.map(str::to_string),
+        };
+        let opportunities = vec![
+            opportunity("24h volume 900 + 0 in flight + 200 exceeds limit 1000", Some("risk_limit")),
+            opportunity("24h volume 950 + 0 in flight + 100 exceeds limit 1000", Some("risk_limit")),
+        ];
+
+        let report = build_report(0, 10, &opportunities, &executions, &[], 1_000);
+
+        assert_eq!(report.total.sent, 3);
+        assert_eq!(report.total.realized_pnl, 30);
+        assert_eq!(report.total.fees, 15);
+        assert_eq!(report.total.capital_utilization, 3.0 * 1_000.0 * 500.0 / (1_000.0 * 10_000.0));
+
+        let jit = &report.by_strategy[0];
+        assert_eq!((jit.key.as_str(), jit.landed, jit.profitable), ("jit", 2, 1));
+        assert_eq!(jit.hit_rate, 0.5);
+        assert_eq!((jit.avg_estimated, jit.avg_realized), (50.0, 15.0));
+
+        let orca = report.by_dex.iter().find(|group| group.key == "orca").unwrap();
+        assert_eq!(orca.realized_pnl, 30);
+        let raydium = report.by_dex.iter().find(|group| group.key == "raydium").unwrap();
+        assert_eq!((raydium.sent, raydium.realized_pnl), (2, 40));
+
+        assert_eq!(
+            report.failures,
+            vec![
+                FailureCount {
+                    stage: "skipped".to_string(),
+                    reason: "risk_limit".to_string(),
+                    count: 2
+                },
+                FailureCount {
+                    stage: "failed".to_string(),
+                    reason: "send_failed".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+}
@@ -1,112 +1,85 @@
+mod admin;
+mod cli;
+mod commands;
 mod config;
 mod core;
+mod dex;
+mod inventory;
+mod journal;
+mod monitoring;
+mod risk;
+mod signer;
 mod strategies;
 mod types;
+mod wallet;
 
 use {
     crate::{
+        cli::{Cli, Command},
+        commands::{
+            run_bot, run_breaker_command, run_config_command, run_keystore_command, run_markets_command,
+            run_quote_command, run_report_command, run_scan_command, run_signer_command, run_simulate_command,
+            run_wallet_command,
+        },
         config::Settings,
-        core::{ArbitrageEngine, ArbitrageStrategy},
-        strategies::StrategyFactory,
+        monitoring::init_logging,
         types::common::ArbitrageError,
     },
-    solana_sdk::{
-        signature::Keypair,
-        signer::Signer,
-    },
-    std::{str::FromStr, env},
-    tokio,
+    clap::Parser,
+    std::time::Duration,
 };
 
-#[tokio::main]
-async fn main() -> Result<(), ArbitrageError> {
-    // Initialize logging
-    env_logger::init();
-    log::info!("Starting Solana Arbitrage Bot...");
-
-    // Load configuration
-    let settings = Settings::load()?;
-    log::info!("Configuration loaded successfully");
-
-    // Load keypair
-    let keypair = load_keypair()?;
-    log::info!("Loaded keypair: {}", keypair.pubkey());
+// After the drain deadline, blocking tasks still running (e.g. a confirmation poll or a signer call) get this long
+// before the process exits without them
+const RUNTIME_SHUTDOWN_GRACE: Duration = Duration::from_secs(1);
 
-    // Initialize arbitrage engine
-    let engine = ArbitrageEngine::new(settings.clone(), keypair)?;
-    log::info!("Arbitrage engine initialized");
+fn main() -> Result<(), ArbitrageError> {
+    let cli = Cli::parse();
 
-    // Initialize strategies
-    let strategies = initialize_strategies(&settings)?;
-    log::info!("Initialized {} strategies", strategies.len());
-
-    // Start the arbitrage bot
-    log::info!("Starting arbitrage operations...");
-    engine.start().await?;
-
-    Ok(())
-}
-
-fn load_keypair() -> Result<Keypair, ArbitrageError> {
-    let keypair_path = env::var("KEYPAIR_PATH")
-        .map_err(|_| ArbitrageError::ConfigError("KEYPAIR_PATH not set".to_string()))?;
+    // Load configuration
+    let overrides = cli.settings_overrides();
+    let settings = Settings::load(cli.config.as_deref(), &overrides)?;
 
-    let keypair_bytes = std::fs::read_to_string(&keypair_path)
-        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to read keypair file: {}", e)))?;
+    // Initialize logging; the guard flushes the JSON log file when dropped
+    let _log_guard = init_logging(&settings.monitoring)?;
+    log::info!("Configuration loaded successfully");
 
-    let keypair_bytes: Vec<u8> = keypair_bytes
-        .trim()
-        .split(',')
-        .map(|s| u8::from_str(s))
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| ArbitrageError::ConfigError(format!("Invalid keypair format: {}", e)))?;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to start the async runtime: {}", e)))?;
+    let result = runtime.block_on(run_command(&cli, settings, overrides));
 
-    Keypair::from_bytes(&keypair_bytes)
-        .map_err(|e| ArbitrageError::ConfigError(format!("Invalid keypair: {}", e)))
+    // Dropping the runtime would wait for every blocking task, so `shutdown_drain_secs` would not be a deadline
+    runtime.shutdown_timeout(RUNTIME_SHUTDOWN_GRACE);
+    result
 }
 
-fn initialize_strategies(settings: &Settings) -> Result<Vec<Box<dyn ArbitrageStrategy>>, ArbitrageError> {
-    let mut strategies = Vec::new();
-
-    // Initialize JIT Liquidity Strategy
-    if settings.trading.execution.execution_strategies.contains(&"jit".to_string()) {
-        strategies.push(StrategyFactory::create_strategy("jit")?);
-    }
-
-    // Initialize Flash Loan Strategy
-    if settings.trading.execution.execution_strategies.contains(&"flash_loan".to_string()) {
-        strategies.push(StrategyFactory::create_strategy("flash_loan")?);
-    }
-
-    // Initialize Front Running Strategy
-    if settings.trading.execution.execution_strategies.contains(&"front_running".to_string()) {
-        strategies.push(StrategyFactory::create_strategy("front_running")?);
+async fn run_command(cli: &Cli, settings: Settings, overrides: Vec<String>) -> Result<(), ArbitrageError> {
+    match &cli.command {
+        None | Some(Command::Run) => run_bot(settings, cli.config.clone(), overrides).await,
+        Some(Command::Scan { once, interval_ms, json }) => {
+            run_scan_command(*once, *interval_ms, *json, &settings).await
+        }
+        Some(Command::Quote { pool, amount }) => run_quote_command(pool, *amount, &settings).await,
+        Some(Command::Markets(command)) => run_markets_command(command, &settings).await,
+        Some(Command::Wallet(command)) => run_wallet_command(command, &settings),
+        Some(Command::Config(command)) => run_config_command(command, &settings),
+        Some(Command::Simulate { path }) => run_simulate_command(path, &settings).await,
+        Some(Command::Keystore(command)) => run_keystore_command(command, &settings),
+        Some(Command::Signer(command)) => run_signer_command(command, &settings).await,
+        Some(Command::Breaker(command)) => run_breaker_command(command, &settings),
+        Some(Command::Report { period, ago, format }) => run_report_command(*period, *ago, *format, &settings),
     }
-
-    Ok(strategies)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn test_strategy_initialization() {
-        let mut settings = Settings::default();
-        settings.trading.execution.execution_strategies = vec![
-            "jit".to_string(),
-            "flash_loan".to_string(),
-            "front_running".to_string(),
-        ];
-
-        let strategies = initialize_strategies(&settings).unwrap();
-        assert_eq!(strategies.len(), 3);
-    }
+    use crate::wallet::load_keypair;
 
     #[test]
     fn test_keypair_loading() {
         // This test requires a valid keypair file to be present
-        std::env::set_var("KEYPAIR_PATH", "test_keypair.json");
-        assert!(load_keypair().is_err()); // Should fail if test file doesn't exist
+        assert!(load_keypair("test_keypair.json", "").is_err()); // Should fail if test file doesn't exist
     }
 }
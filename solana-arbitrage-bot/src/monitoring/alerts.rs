@@ -0,0 +1,76 @@
+use {
+    serde::{Deserialize, Serialize},
+    std::{
+        fmt,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::mpsc,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub timestamp: i64,
+    pub severity: AlertSeverity,
+    // Component that raised the alert, e.g. `circuit_breaker`
+    pub source: String,
+    pub message: String,
+    // Repeats of the same source and key are deduplicated; defaults to the message
+    pub key: String,
+}
+
+impl Alert {
+    pub fn new(severity: AlertSeverity, source: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            severity,
+            source: source.to_string(),
+            key: message.clone(),
+            message,
+        }
+    }
+
+    // For alerts whose message carries changing numbers, e.g. how stale a market is
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:?}] {}: {}", self.severity, self.source, self.message)
+    }
+}
+
+// Cheap to clone; raising an alert never blocks or fails the caller
+#[derive(Clone)]
+pub struct AlertSender {
+    sender: mpsc::UnboundedSender<Alert>,
+}
+
+pub type AlertReceiver = mpsc::UnboundedReceiver<Alert>;
+
+pub fn alert_channel() -> (AlertSender, AlertReceiver) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    (AlertSender { sender }, receiver)
+}
+
+impl AlertSender {
+    pub fn send(&self, alert: Alert) {
+        if self.sender.send(alert.clone()).is_err() {
+            log::warn!("Alert dropped, no dispatcher running: {}", alert);
+        }
+    }
+}
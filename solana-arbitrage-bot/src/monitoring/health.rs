@@ -0,0 +1,275 @@
+use {
+    super::watchdog::{host, probe},
+    crate::{
+        config::SettingsHandle,
+        core::ArbitrageEngine,
+        risk::{BreakerState, CircuitBreaker},
+        signer::RemoteSigner,
+        wallet::ExecutionPool,
+    },
+    axum::{extract::State, http::StatusCode, routing::get, Json, Router},
+    serde::Serialize,
+    solana_sdk::signer::Signer,
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, RwLock},
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+// Older results mean the refresh itself is stuck, e.g. on an RPC endpoint that never answers
+const MAX_RESULT_AGE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Check {
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl Check {
+    fn pass(detail: impl Into<String>) -> Self {
+        Self { ok: true, detail: detail.into() }
+    }
+
+    fn fail(detail: impl Into<String>) -> Self {
+        Self { ok: false, detail: detail.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    // True when every check passes
+    pub ready: bool,
+    pub checks: BTreeMap<&'static str, Check>,
+}
+
+impl HealthReport {
+    fn new(checks: BTreeMap<&'static str, Check>) -> Self {
+        Self {
+            ready: checks.values().all(|check| check.ok),
+            checks,
+        }
+    }
+}
+
+// Inputs for `/healthz` and `/readyz`. The checks run in the background and are cached, so requests never wait
+// on I/O.
+pub struct HealthChecks {
+    settings: SettingsHandle,
+    engine: Arc<ArbitrageEngine>,
+    wallets: Arc<ExecutionPool>,
+    breaker: Arc<CircuitBreaker>,
+    remote_signer: Option<Arc<RemoteSigner>>,
+    latest: RwLock<Option<(BTreeMap<&'static str, Check>, Instant)>>,
+}
+
+impl HealthChecks {
+    pub fn new(
+        settings: SettingsHandle,
+        engine: Arc<ArbitrageEngine>,
+        wallets: Arc<ExecutionPool>,
+        breaker: Arc<CircuitBreaker>,
+        remote_signer: Option<Arc<RemoteSigner>>,
+    ) -> Self {
+        Self {
+            settings,
+            engine,
+            wallets,
+            breaker,
+            remote_signer,
+            latest: RwLock::new(None),
+        }
+    }
+
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            // Probes use the blocking RPC client, signer socket and breaker file, so they run on the blocking pool
+            let market_slot = self.engine.latest_market_slot().await;
+            let checks = self.clone();
+            match tokio::task::spawn_blocking(move || checks.run_checks(market_slot)).await {
+                Ok(checks) => *self.latest.write().unwrap() = Some((checks, Instant::now())),
+                Err(e) => log::error!("Health check task failed: {}", e),
+            }
+        }
+    }
+
+    // The cached checks and how long ago they ran; never does I/O
+    pub fn report(&self) -> HealthReport {
+        let (mut checks, age) = match &*self.latest.read().unwrap() {
+            Some((checks, checked_at)) => (checks.clone(), Some(checked_at.elapsed())),
+            None => (BTreeMap::new(), None),
+        };
+        checks.insert("checked", freshness_check(age));
+
+        HealthReport::new(checks)
+    }
+
+    fn run_checks(&self, market_slot: u64) -> BTreeMap<&'static str, Check> {
+        let settings = self.settings.load();
+        let mut checks = BTreeMap::new();
+
+        // The first endpoint that answers also supplies the slot and balances for the other checks
+        let network = &settings.network;
+        let healthy = network
+            .rpc_endpoints
+            .iter()
+            .chain(&network.backup_nodes)
+            .find_map(|endpoint| probe(endpoint).ok().map(|(client, slot)| (endpoint, client, slot)));
+
+        match &healthy {
+            Some((endpoint, client, slot)) => {
+                checks.insert("rpc", Check::pass(format!("{} at slot {}", host(endpoint), slot)));
+                checks.insert(
+                    "market_data",
+                    market_check(market_slot, *slot, settings.monitoring.health_max_market_lag_slots),
+                );
+
+                let balances: Vec<(String, Option<u64>)> = self
+                    .wallets
+                    .wallets()
+                    .iter()
+                    .map(|wallet| (wallet.pubkey().to_string(), client.get_balance(&wallet.pubkey()).ok()))
+                    .collect();
+                checks.insert("sol_balance", balance_check(&balances, settings.wallet.fee_reserve_lamports));
+            }
+            None => {
+                checks.insert("rpc", Check::fail("no RPC endpoint answers"));
+                checks.insert("market_data", Check::fail("no RPC endpoint to compare against"));
+                checks.insert("sol_balance", Check::fail("no RPC endpoint to read balances from"));
+            }
+        }
+
+        let signer = match &self.remote_signer {
+            Some(remote) => match remote.ping() {
+                Ok(()) => Check::pass(format!("remote signer {} answers", remote.pubkey())),
+                Err(e) => Check::fail(e.to_string()),
+            },
+            None if self.wallets.wallets().is_empty() => Check::fail("no execution wallets loaded"),
+            None => Check::pass(format!("{} local wallets unlocked", self.wallets.wallets().len())),
+        };
+        checks.insert("signer", signer);
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+        checks.insert("circuit_breaker", breaker_check(&self.breaker.state(), now));
+
+        checks
+    }
+}
+
+fn freshness_check(age: Option<Duration>) -> Check {
+    match age {
+        None => Check::fail("checks have not run yet"),
+        Some(age) if age > MAX_RESULT_AGE => Check::fail(format!("checks last ran {}s ago", age.as_secs())),
+        Some(age) => Check::pass(format!("checks ran {}s ago", age.as_secs())),
+    }
+}
+
+fn market_check(latest_market_slot: u64, current_slot: u64, max_lag: u64) -> Check {
+    if latest_market_slot == 0 {
+        return Check::fail("no market data loaded");
+    }
+    let lag = current_slot.saturating_sub(latest_market_slot);
+    if lag > max_lag {
+        Check::fail(format!("newest market data is {} slots behind (limit {})", lag, max_lag))
+    } else {
+        Check::pass(format!("newest market data is {} slots behind", lag))
+    }
+}
+
+// Passes while at least one wallet can still pay fees; names the ones that cannot
+fn balance_check(balances: &[(String, Option<u64>)], floor: u64) -> Check {
+    let low: Vec<&str> = balances
+        .iter()
+        .filter(|(_, lamports)| lamports.map_or(true, |lamports| lamports < floor))
+        .map(|(wallet, _)| wallet.as_str())
+        .collect();
+
+    if low.len() == balances.len() {
+        Check::fail(format!("no wallet holds more than the {} lamport fee floor", floor))
+    } else if low.is_empty() {
+        Check::pass(format!("{} wallets above the fee floor", balances.len()))
+    } else {
+        Check::pass(format!("below the fee floor: {}", low.join(", ")))
+    }
+}
+
+fn breaker_check(state: &BreakerState, now: i64) -> Check {
+    let open: Vec<String> = state
+        .active(now)
+        .map(|(scope, trip)| format!("{} ({}s left)", scope, trip.until - now))
+        .collect();
+
+    if open.is_empty() {
+        Check::pass("no breakers open")
+    } else {
+        Check::fail(format!("open: {}", open.join(", ")))
+    }
+}
+
+// `/healthz` answers 200 whenever the process can serve it; `/readyz` answers 503 until
+// every check passes. Both return the individual checks from the last refresh.
+pub fn health_routes(checks: Arc<HealthChecks>) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(checks)
+}
+
+async fn healthz(State(checks): State<Arc<HealthChecks>>) -> Json<HealthReport> {
+    Json(checks.report())
+}
+
+async fn readyz(State(checks): State<Arc<HealthChecks>>) -> (StatusCode, Json<HealthReport>) {
+    let report = checks.report();
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::risk::Trip};
+
+    #[test]
+    fn test_checks_report_individually() {
+        assert!(market_check(1_000, 1_010, 32).ok);
+        assert!(!market_check(1_000, 1_100, 32).ok);
+        assert!(!market_check(0, 1_100, 32).ok);
+
+        let balances = vec![("a".to_string(), Some(10_000)), ("b".to_string(), Some(10))];
+        assert_eq!(balance_check(&balances, 5_000), Check::pass("below the fee floor: b"));
+        assert!(!balance_check(&balances, 50_000).ok);
+
+        let mut state = BreakerState::default();
+        assert!(breaker_check(&state, 100).ok);
+        state.trips.insert(
+            "strategy:jit".to_string(),
+            Trip {
+                reason: "3 consecutive failures".to_string(),
+                tripped_at: 40,
+                until: 160,
+            },
+        );
+        assert_eq!(breaker_check(&state, 100), Check::fail("open: strategy:jit (60s left)"));
+        assert!(breaker_check(&state, 200).ok);
+
+        assert!(!freshness_check(None).ok);
+        assert!(freshness_check(Some(Duration::from_secs(5))).ok);
+        assert!(!freshness_check(Some(Duration::from_secs(300))).ok);
+
+        let mut checks = BTreeMap::new();
+        checks.insert("rpc", Check::pass("ok"));
+        checks.insert("circuit_breaker", breaker_check(&state, 100));
+        let report = serde_json::to_value(HealthReport::new(checks)).unwrap();
+        assert_eq!(report["ready"], false);
+        assert_eq!(report["checks"]["rpc"]["ok"], true);
+        assert_eq!(report["checks"]["circuit_breaker"]["ok"], false);
+    }
+}
@@ -0,0 +1,23 @@
+use {
+    crate::types::common::ArbitrageError,
+    axum::Router,
+    std::net::{SocketAddr, TcpListener},
+};
+
+pub async fn serve_http(address: SocketAddr, router: Router) -> Result<(), ArbitrageError> {
+    let listener = TcpListener::bind(address)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to bind HTTP server on {}: {}", address, e)))?;
+    log::info!("Serving monitoring endpoints on http://{}", address);
+    serve_http_on(listener, router).await
+}
+
+pub async fn serve_http_on(listener: TcpListener, router: Router) -> Result<(), ArbitrageError> {
+    let failed = |e: &dyn std::fmt::Display| ArbitrageError::NetworkError(format!("HTTP server failed: {}", e));
+    listener.set_nonblocking(true).map_err(|e| failed(&e))?;
+
+    axum::Server::from_tcp(listener)
+        .map_err(|e| failed(&e))?
+        .serve(router.into_make_service())
+        .await
+        .map_err(|e| failed(&e))
+}
@@ -0,0 +1,128 @@
+use {
+    crate::{config::MonitoringSettings, types::common::ArbitrageError},
+    std::{fs::OpenOptions, path::Path},
+    tracing::Subscriber,
+    tracing_appender::non_blocking::WorkerGuard,
+    tracing_subscriber::{
+        fmt::{format::FmtSpan, MakeWriter},
+        layer::SubscriberExt,
+        registry::LookupSpan,
+        util::SubscriberInitExt,
+        EnvFilter, Layer,
+    },
+};
+
+// Logs go to stderr as text or JSON per `log_format` and, with `log_file` set, one JSON object per line
+// to that file. `RUST_LOG` overrides `log_level` when present. Keep the returned guard
+// alive until exit; dropping it flushes the file.
+pub fn init_logging(monitoring: &MonitoringSettings) -> Result<Option<WorkerGuard>, ArbitrageError> {
+    let filter = match std::env::var("RUST_LOG") {
+        Ok(directives) => EnvFilter::new(directives),
+        Err(_) => EnvFilter::new(&monitoring.log_level),
+    };
+
+    let (file_layer, guard) = if monitoring.log_file.is_empty() {
+        (None, None)
+    } else {
+        let path = Path::new(&monitoring.log_file);
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                ArbitrageError::ConfigError(format!("Failed to create log directory {}: {}", parent.display(), e))
+            })?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to open log file {}: {}", path.display(), e)))?;
+        // Writes happen on a background thread so logging never stalls a trade
+        let (writer, guard) = tracing_appender::non_blocking(file);
+        (Some(json_layer(writer)), Some(guard))
+    };
+
+    // Validation guarantees the format is `text` or `json`
+    let stderr_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    let (text_layer, json_stderr_layer) = match monitoring.log_format.as_str() {
+        "json" => (None, Some(stderr_layer.json())),
+        _ => (Some(stderr_layer), None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(text_layer)
+        .with(json_stderr_layer)
+        .with(file_layer)
+        .try_init()
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to initialize logging: {}", e)))?;
+
+    Ok(guard)
+}
+
+// Every span is written when it closes with its busy and idle time, so the stages of a
+// trade (`quote`, `build`, `simulate`, `send`, `confirm`) can be read back per opportunity
+fn json_layer<S, W>(writer: W) -> impl Layer<S> + Send + Sync
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_writer(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::Value,
+        std::{
+            io,
+            sync::{Arc, Mutex},
+        },
+    };
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_log_carries_opportunity_span() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(json_layer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let opportunity = tracing::info_span!("opportunity", id = 7u64, strategy = "jit");
+            opportunity.in_scope(|| {
+                tracing::info_span!("build").in_scope(|| tracing::info!("built"));
+            });
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        let built = lines.iter().find(|line| line["fields"]["message"] == "built").unwrap();
+        assert_eq!(built["spans"][0]["id"], 7);
+        assert_eq!(built["span"]["name"], "build");
+
+        let closed = lines
+            .iter()
+            .find(|line| line["fields"]["message"] == "close" && line["span"]["name"] == "build")
+            .unwrap();
+        assert!(closed["fields"]["time.busy"].is_string());
+        assert_eq!(closed["spans"][0]["strategy"], "jit");
+    }
+}
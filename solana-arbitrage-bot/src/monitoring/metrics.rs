@@ -0,0 +1,220 @@
+use {
+    crate::{types::common::ArbitrageError, wallet::ExecutionPool},
+    axum::{
+        extract::State,
+        http::{header, StatusCode},
+        response::{IntoResponse, Response},
+        routing::get,
+        Router,
+    },
+    prometheus::{
+        Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+    },
+    std::{path::Path, sync::Arc, time::Duration},
+};
+
+const WALLET_BALANCE_INTERVAL: Duration = Duration::from_secs(30);
+
+// Prometheus collectors for the trading loop, served at `/metrics`
+pub struct Metrics {
+    registry: Registry,
+    opportunities_found: IntCounterVec,
+    opportunities_rejected: IntCounterVec,
+    sends: IntCounterVec,
+    lands: IntCounterVec,
+    failures: IntCounterVec,
+    realized_pnl: IntGaugeVec,
+    quote_to_send: Histogram,
+    rpc_latency: HistogramVec,
+    market_lag: Histogram,
+    wallet_balance: IntGaugeVec,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, ArbitrageError> {
+        let failed = |e: prometheus::Error| ArbitrageError::ConfigError(format!("Failed to create metrics: {}", e));
+        let registry = Registry::new_custom(Some("arb".to_string()), None).map_err(failed)?;
+
+        let counter = |name: &str, help: &str, labels: &[&str]| -> Result<IntCounterVec, ArbitrageError> {
+            let counter = IntCounterVec::new(Opts::new(name, help), labels).map_err(failed)?;
+            registry.register(Box::new(counter.clone())).map_err(failed)?;
+            Ok(counter)
+        };
+        let gauge = |name: &str, help: &str, labels: &[&str]| -> Result<IntGaugeVec, ArbitrageError> {
+            let gauge = IntGaugeVec::new(Opts::new(name, help), labels).map_err(failed)?;
+            registry.register(Box::new(gauge.clone())).map_err(failed)?;
+            Ok(gauge)
+        };
+
+        let quote_to_send = Histogram::with_opts(
+            HistogramOpts::new("quote_to_send_seconds", "Time from finding an opportunity to sending it")
+                .buckets(vec![0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0]),
+        )
+        .map_err(failed)?;
+        let rpc_latency = HistogramVec::new(
+            HistogramOpts::new("rpc_request_seconds", "RPC request latency")
+                .buckets(vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5]),
+            &["endpoint", "method"],
+        )
+        .map_err(failed)?;
+        let market_lag = Histogram::with_opts(
+            HistogramOpts::new("market_data_lag_slots", "Slots between a market observation and the current slot")
+                .buckets(vec![0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]),
+        )
+        .map_err(failed)?;
+        for histogram in [Box::new(quote_to_send.clone()), Box::new(market_lag.clone())] {
+            registry.register(histogram).map_err(failed)?;
+        }
+        registry.register(Box::new(rpc_latency.clone())).map_err(failed)?;
+
+        Ok(Self {
+            opportunities_found: counter("opportunities_found_total", "Opportunities found", &["strategy"])?,
+            opportunities_rejected: counter(
+                "opportunities_rejected_total",
+                "Opportunities not sent, by reason",
+                &["reason"],
+            )?,
+            sends: counter("transactions_sent_total", "Transactions sent", &["strategy"])?,
+            lands: counter("transactions_landed_total", "Transactions confirmed", &["strategy"])?,
+            failures: counter("transactions_failed_total", "Transactions that failed to send or land", &["strategy"])?,
+            realized_pnl: gauge("realized_pnl_lamports", "Realized PnL since startup", &["strategy"])?,
+            wallet_balance: gauge("wallet_balance_lamports", "SOL balance of each execution wallet", &["wallet"])?,
+            quote_to_send,
+            rpc_latency,
+            market_lag,
+            registry,
+        })
+    }
+
+    pub fn opportunity_found(&self, strategy: &str) {
+        self.opportunities_found.with_label_values(&[strategy]).inc();
+    }
+
+    // `reason` must come from a small fixed set to keep label cardinality bounded
+    pub fn opportunity_rejected(&self, reason: &str) {
+        self.opportunities_rejected.with_label_values(&[reason]).inc();
+    }
+
+    pub fn trade_sent(&self, strategy: &str) {
+        self.sends.with_label_values(&[strategy]).inc();
+    }
+
+    pub fn trade_landed(&self, strategy: &str, realized_pnl: Option<i64>) {
+        self.lands.with_label_values(&[strategy]).inc();
+        if let Some(pnl) = realized_pnl {
+            self.realized_pnl.with_label_values(&[strategy]).add(pnl);
+        }
+    }
+
+    pub fn trade_failed(&self, strategy: &str) {
+        self.failures.with_label_values(&[strategy]).inc();
+    }
+
+    pub fn observe_quote_to_send(&self, elapsed: Duration) {
+        self.quote_to_send.observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_rpc(&self, endpoint: &str, method: &str, elapsed: Duration) {
+        self.rpc_latency
+            .with_label_values(&[endpoint_label(endpoint).as_str(), method])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_market_lag(&self, slots: u64) {
+        self.market_lag.observe(slots as f64);
+    }
+
+    pub fn set_wallet_balance(&self, wallet: &str, lamports: u64) {
+        self.wallet_balance.with_label_values(&[wallet]).set(lamports as i64);
+    }
+
+    pub fn render(&self) -> Result<String, ArbitrageError> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode metrics: {}", e)))?;
+        String::from_utf8(buffer).map_err(|e| ArbitrageError::ConfigError(format!("Metrics are not UTF-8: {}", e)))
+    }
+
+    // Written beside the target and renamed over it, so a textfile collector never reads half a file
+    pub fn write_snapshot(&self, path: &Path) -> Result<(), ArbitrageError> {
+        let failed = |e: std::io::Error| {
+            ArbitrageError::ConfigError(format!("Failed to write metrics snapshot {}: {}", path.display(), e))
+        };
+        let staging = path.with_extension("tmp");
+        std::fs::write(&staging, self.render()?).map_err(failed)?;
+        std::fs::rename(&staging, path).map_err(failed)
+    }
+}
+
+// Host and port only, so API keys in paths or query strings never end up in a label
+fn endpoint_label(endpoint: &str) -> String {
+    match reqwest::Url::parse(endpoint) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => "unknown".to_string(),
+        },
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+pub fn metrics_routes(metrics: Arc<Metrics>) -> Router {
+    Router::new().route("/metrics", get(scrape)).with_state(metrics)
+}
+
+async fn scrape(State(metrics): State<Arc<Metrics>>) -> Response {
+    match metrics.render() {
+        Ok(body) => ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], body).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+// Balances come from RPC, so they are polled rather than updated on every trade
+pub async fn track_wallet_balances(metrics: Arc<Metrics>, wallets: Arc<ExecutionPool>) {
+    let mut interval = tokio::time::interval(WALLET_BALANCE_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        match wallets.blocking(|wallets| wallets.report()).await {
+            Ok(reports) => {
+                for report in reports {
+                    metrics.set_wallet_balance(&report.pubkey.to_string(), report.lamports);
+                }
+            }
+            Err(e) => log::warn!("Failed to read wallet balances for metrics: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::monitoring::serve_http_on, std::net::TcpListener};
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_serves_recorded_values() {
+        let metrics = Arc::new(Metrics::new().unwrap());
+        metrics.opportunity_found("jit");
+        metrics.opportunity_rejected("risk_limit");
+        metrics.trade_sent("jit");
+        metrics.trade_landed("jit", Some(-1_500));
+        metrics.observe_rpc("https://rpc.example.com/secret-key?api-key=abc", "getSlot", Duration::from_millis(20));
+        metrics.observe_market_lag(3);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve_http_on(listener, metrics_routes(metrics)));
+
+        let response = reqwest::get(format!("http://{}/metrics", address)).await.unwrap();
+        assert!(response.status().is_success());
+        let body = response.text().await.unwrap();
+
+        assert!(body.contains("arb_opportunities_found_total{strategy=\"jit\"} 1"));
+        assert!(body.contains("arb_opportunities_rejected_total{reason=\"risk_limit\"} 1"));
+        assert!(body.contains("arb_transactions_landed_total{strategy=\"jit\"} 1"));
+        assert!(body.contains("arb_realized_pnl_lamports{strategy=\"jit\"} -1500"));
+        assert!(body.contains("arb_rpc_request_seconds_count{endpoint=\"rpc.example.com\",method=\"getSlot\"} 1"));
+        assert!(body.contains("arb_market_data_lag_slots_count 1"));
+        assert!(!body.contains("secret-key"));
+    }
+}
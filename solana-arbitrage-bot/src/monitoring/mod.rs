@@ -0,0 +1,15 @@
+mod alerts;
+mod health;
+mod http;
+mod logging;
+mod metrics;
+mod watchdog;
+mod webhooks;
+
+pub use alerts::*;
+pub use health::*;
+pub use http::*;
+pub use logging::*;
+pub use metrics::*;
+pub use watchdog::*;
+pub use webhooks::*;
@@ -0,0 +1,128 @@
+use {
+    super::{Alert, AlertSender, AlertSeverity},
+    crate::{config::SettingsHandle, wallet::ExecutionPool},
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, signer::Signer},
+    std::{sync::Arc, time::Duration},
+};
+
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(30);
+
+// Polls the RPC endpoints and execution wallet balances, alerting when the primary
+// endpoint stops answering or a wallet can no longer cover its fees
+pub struct Watchdog {
+    settings: SettingsHandle,
+    wallets: Arc<ExecutionPool>,
+    alerts: AlertSender,
+}
+
+impl Watchdog {
+    pub fn new(settings: SettingsHandle, wallets: Arc<ExecutionPool>, alerts: AlertSender) -> Self {
+        Self {
+            settings,
+            wallets,
+            alerts,
+        }
+    }
+
+    pub async fn run(self) {
+        let watchdog = Arc::new(self);
+        let mut interval = tokio::time::interval(WATCHDOG_INTERVAL);
+        let mut primary_down = false;
+        loop {
+            interval.tick().await;
+
+            // Probes and balance reads use the blocking RPC client, so they run on the blocking pool
+            let check = watchdog.clone();
+            match tokio::task::spawn_blocking(move || check.check(primary_down)).await {
+                Ok(down) => primary_down = down,
+                Err(e) => log::error!("Watchdog check failed: {}", e),
+            }
+        }
+    }
+
+    // One pass over the endpoints and wallets; returns whether the primary endpoint is down
+    fn check(&self, primary_down: bool) -> bool {
+        let settings = self.settings.load();
+        let network = &settings.network;
+        let primary = &network.rpc_endpoints[0];
+        let (client, down) = match probe(primary) {
+            Ok((client, _)) => {
+                if primary_down {
+                    self.alerts.send(Alert::new(
+                        AlertSeverity::Info,
+                        "rpc_failover",
+                        format!("Primary RPC {} is healthy again", host(primary)),
+                    ));
+                }
+                (client, false)
+            }
+            Err(e) => {
+                // Errors can echo the URL, so the detail only goes to the log
+                log::warn!("Primary RPC {} failed its health check: {}", host(primary), e);
+                let backup = network
+                    .rpc_endpoints
+                    .iter()
+                    .skip(1)
+                    .chain(&network.backup_nodes)
+                    .find_map(|endpoint| probe(endpoint).ok().map(|(client, _)| (endpoint, client)));
+                let message = match &backup {
+                    Some((endpoint, _)) => {
+                        format!("Primary RPC {} is failing, backup {} is answering", host(primary), host(endpoint))
+                    }
+                    None => format!("Primary RPC {} is failing and no backup answers", host(primary)),
+                };
+                self.alerts.send(
+                    Alert::new(AlertSeverity::Critical, "rpc_failover", message).with_key(host(primary)),
+                );
+                match backup {
+                    Some((_, client)) => (client, true),
+                    None => return true,
+                }
+            }
+        };
+
+        let floor = settings.wallet.fee_reserve_lamports;
+        for wallet in self.wallets.wallets() {
+            let pubkey = wallet.pubkey();
+            match client.get_balance(&pubkey) {
+                Ok(lamports) if lamports < floor => self.alerts.send(
+                    Alert::new(
+                        AlertSeverity::Warning,
+                        "low_balance",
+                        format!(
+                            "Wallet {} has {:.4} SOL, below the {:.4} SOL fee reserve",
+                            pubkey,
+                            lamports as f64 / 1e9,
+                            floor as f64 / 1e9
+                        ),
+                    )
+                    .with_key(pubkey.to_string()),
+                ),
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to read balance of {}: {}", pubkey, e),
+            }
+        }
+
+        down
+    }
+}
+
+// A client for `endpoint` and its current slot, if it answers
+pub(super) fn probe(endpoint: &str) -> Result<(RpcClient, u64), String> {
+    let client = RpcClient::new_with_timeout_and_commitment(
+        endpoint.to_string(),
+        Duration::from_secs(5),
+        CommitmentConfig::confirmed(),
+    );
+    let slot = client.get_slot().map_err(|e| e.to_string())?;
+    Ok((client, slot))
+}
+
+// RPC URLs often carry API keys, so alerts only name the host
+pub(super) fn host(endpoint: &str) -> String {
+    reqwest::Url::parse(endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string())
+}
@@ -0,0 +1,284 @@
+use {
+    super::{Alert, AlertReceiver, AlertSeverity},
+    crate::{config::SettingsHandle, types::common::ArbitrageError},
+    serde_json::{json, Value},
+    std::{
+        collections::{HashMap, VecDeque},
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+// Discord rejects messages longer than this
+const DISCORD_MAX_CONTENT: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    Json,
+    Slack,
+    Discord,
+}
+
+// An `alert_endpoints` entry: a plain URL receives the alert as JSON, while
+// `slack+https://...` and `discord+https://...` post in that service's webhook format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub url: String,
+    pub format: WebhookFormat,
+}
+
+impl Webhook {
+    pub fn parse(endpoint: &str) -> Result<Self, ArbitrageError> {
+        let (format, url) = match endpoint.split_once('+') {
+            Some(("slack", url)) => (WebhookFormat::Slack, url),
+            Some(("discord", url)) => (WebhookFormat::Discord, url),
+            Some(("json", url)) => (WebhookFormat::Json, url),
+            _ => (WebhookFormat::Json, endpoint),
+        };
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(ArbitrageError::ConfigError(format!(
+                "`{}` is not an http(s) URL, optionally prefixed with slack+, discord+ or json+",
+                endpoint
+            )));
+        }
+
+        Ok(Self {
+            url: url.to_string(),
+            format,
+        })
+    }
+
+    fn payload(&self, alert: &Alert, suppressed: u64) -> Value {
+        let mut text = format!("{} {}: {}", severity_marker(alert.severity), alert.source, alert.message);
+        if suppressed > 0 {
+            text.push_str(&format!(" ({} similar alerts suppressed)", suppressed));
+        }
+
+        match self.format {
+            WebhookFormat::Json => json!({
+                "timestamp": alert.timestamp,
+                "severity": alert.severity,
+                "source": alert.source,
+                "message": alert.message,
+                "suppressed": suppressed,
+            }),
+            WebhookFormat::Slack => json!({ "text": text }),
+            WebhookFormat::Discord => json!({ "content": text.chars().take(DISCORD_MAX_CONTENT).collect::<String>() }),
+        }
+    }
+}
+
+fn severity_marker(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "[INFO]",
+        AlertSeverity::Warning => "[WARNING]",
+        AlertSeverity::Critical => "[CRITICAL]",
+    }
+}
+
+// Drops repeats of an alert inside the dedupe window and caps how many alerts each
+// source may send per minute, counting what was held back
+#[derive(Default)]
+struct AlertLimiter {
+    last_seen: HashMap<(String, String), Instant>,
+    sent: HashMap<String, VecDeque<Instant>>,
+    suppressed: HashMap<String, u64>,
+}
+
+impl AlertLimiter {
+    // Returns how many alerts from the same source were suppressed since the last one
+    // sent, or `None` when this alert should be dropped
+    fn admit(&mut self, alert: &Alert, now: Instant, dedupe: Duration, per_minute: u32) -> Option<u64> {
+        let key = (alert.source.clone(), alert.key.clone());
+        if let Some(seen) = self.last_seen.get(&key) {
+            if now.duration_since(*seen) < dedupe {
+                *self.suppressed.entry(alert.source.clone()).or_default() += 1;
+                return None;
+            }
+        }
+
+        let sent = self.sent.entry(alert.source.clone()).or_default();
+        while sent.front().map_or(false, |at| now.duration_since(*at) >= RATE_WINDOW) {
+            sent.pop_front();
+        }
+        if sent.len() >= per_minute as usize {
+            *self.suppressed.entry(alert.source.clone()).or_default() += 1;
+            return None;
+        }
+
+        sent.push_back(now);
+        self.last_seen.retain(|_, seen| now.duration_since(*seen) < dedupe);
+        self.last_seen.insert(key, now);
+        Some(self.suppressed.remove(&alert.source).unwrap_or(0))
+    }
+}
+
+// Logs every alert and posts it to each of `monitoring.alert_endpoints`
+pub struct AlertDispatcher {
+    settings: SettingsHandle,
+    client: reqwest::Client,
+    limiter: Mutex<AlertLimiter>,
+}
+
+impl AlertDispatcher {
+    pub fn new(settings: SettingsHandle) -> Result<Self, ArbitrageError> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| ArbitrageError::NetworkError(format!("Failed to create webhook client: {}", e)))?;
+
+        Ok(Self {
+            settings,
+            client,
+            limiter: Mutex::new(AlertLimiter::default()),
+        })
+    }
+
+    pub async fn run(&self, mut receiver: AlertReceiver) {
+        while let Some(alert) = receiver.recv().await {
+            self.dispatch(&alert).await;
+        }
+    }
+
+    pub async fn dispatch(&self, alert: &Alert) {
+        match alert.severity {
+            AlertSeverity::Info => log::info!("Alert {}", alert),
+            AlertSeverity::Warning => log::warn!("Alert {}", alert),
+            AlertSeverity::Critical => log::error!("Alert {}", alert),
+        }
+
+        let settings = self.settings.load();
+        let monitoring = &settings.monitoring;
+        if monitoring.alert_endpoints.is_empty() {
+            return;
+        }
+
+        let suppressed = match self.limiter.lock().unwrap().admit(
+            alert,
+            Instant::now(),
+            Duration::from_secs(monitoring.alert_dedupe_secs),
+            monitoring.alert_rate_limit,
+        ) {
+            Some(suppressed) => suppressed,
+            None => {
+                log::debug!("Alert rate limited: {}", alert);
+                return;
+            }
+        };
+
+        for endpoint in &monitoring.alert_endpoints {
+            // Endpoints are checked when settings load, so a bad one here has been skipped already
+            let webhook = match Webhook::parse(endpoint) {
+                Ok(webhook) => webhook,
+                Err(_) => continue,
+            };
+            if let Err(e) = self.post(&webhook, &webhook.payload(alert, suppressed)).await {
+                log::warn!("{}", e);
+            }
+        }
+    }
+
+    async fn post(&self, webhook: &Webhook, payload: &Value) -> Result<(), ArbitrageError> {
+        // Keep tokens in webhook paths out of the logs
+        let host = reqwest::Url::parse(&webhook.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let response = self
+            .client
+            .post(&webhook.url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| {
+                ArbitrageError::NetworkError(format!("Alert webhook to {} failed: {}", host, e.without_url()))
+            })?;
+        if !response.status().is_success() {
+            return Err(ArbitrageError::NetworkError(format!(
+                "Alert webhook to {} returned {}",
+                host,
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::config::Settings,
+        axum::{extract::State, routing::post, Json, Router},
+        std::{net::TcpListener, sync::Arc},
+    };
+
+    type Received = Arc<Mutex<Vec<(String, Value)>>>;
+
+    async fn record(State(received): State<Received>, uri: axum::http::Uri, Json(body): Json<Value>) {
+        received.lock().unwrap().push((uri.path().to_string(), body));
+    }
+
+    fn spawn_sink() -> (String, Received) {
+        let received = Received::default();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/:hook", post(record))
+            .with_state(received.clone());
+        tokio::spawn(axum::Server::from_tcp(listener).unwrap().serve(app.into_make_service()));
+        (format!("http://{}", address), received)
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_formats_and_dedupes_per_endpoint() {
+        let (sink, received) = spawn_sink();
+        let mut settings = Settings::default();
+        settings.monitoring.alert_endpoints = vec![
+            format!("{}/json", sink),
+            format!("slack+{}/slack", sink),
+            format!("discord+{}/discord", sink),
+        ];
+        let dispatcher = AlertDispatcher::new(SettingsHandle::new(settings)).unwrap();
+
+        let trip = Alert::new(AlertSeverity::Critical, "circuit_breaker", "strategy:jit paused for 60s");
+        dispatcher.dispatch(&trip).await;
+        dispatcher.dispatch(&trip).await;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 3);
+        let body = |hook: &str| &received.iter().find(|(path, _)| path == hook).unwrap().1;
+        assert_eq!(body("/json")["source"], "circuit_breaker");
+        assert_eq!(body("/json")["severity"], "critical");
+        assert_eq!(body("/slack")["text"], "[CRITICAL] circuit_breaker: strategy:jit paused for 60s");
+        assert_eq!(body("/discord")["content"], "[CRITICAL] circuit_breaker: strategy:jit paused for 60s");
+    }
+
+    #[test]
+    fn test_limiter_caps_each_source_and_reports_suppressed() {
+        let mut limiter = AlertLimiter::default();
+        let start = Instant::now();
+        let dedupe = Duration::from_secs(300);
+        let alert = |message: &str| Alert::new(AlertSeverity::Warning, "market_data", message);
+
+        assert_eq!(limiter.admit(&alert("a"), start, dedupe, 2), Some(0));
+        assert_eq!(limiter.admit(&alert("a"), start, dedupe, 2), None);
+        assert_eq!(limiter.admit(&alert("b"), start, dedupe, 2), Some(1));
+        assert_eq!(limiter.admit(&alert("c"), start, dedupe, 2), None);
+
+        // Another source has its own budget
+        assert_eq!(
+            limiter.admit(&Alert::new(AlertSeverity::Info, "process", "a"), start, dedupe, 2),
+            Some(0)
+        );
+
+        let later = start + RATE_WINDOW;
+        assert_eq!(limiter.admit(&alert("c"), later, dedupe, 2), Some(1));
+        assert_eq!(limiter.admit(&alert("a"), start + dedupe, dedupe, 2), Some(0));
+    }
+}
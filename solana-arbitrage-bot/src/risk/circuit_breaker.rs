@@ -0,0 +1,348 @@
+use {
+    crate::{
+        config::SettingsHandle,
+        monitoring::{Alert, AlertSender, AlertSeverity},
+        types::common::{ArbitrageError, ArbitrageOpportunity},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::{BTreeMap, HashMap},
+        path::{Path, PathBuf},
+        sync::Mutex,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+// How a trade that reached the network ended
+#[derive(Debug, Clone, Copy)]
+pub enum TradeOutcome {
+    // Send failed, or the transaction expired or errored on chain
+    Failed,
+    Landed { simulated_profit: i64, realized_profit: i64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trip {
+    pub reason: String,
+    pub tripped_at: i64,
+    pub until: i64,
+}
+
+// Tripped scopes keyed as `strategy:<name>` or `market:<pubkey>`; shared with the CLI through the state file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BreakerState {
+    pub trips: BTreeMap<String, Trip>,
+}
+
+impl BreakerState {
+    pub fn load(path: &Path) -> Result<Self, ArbitrageError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ArbitrageError::ConfigError(format!("Failed to read breaker state {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| {
+            ArbitrageError::ConfigError(format!("Breaker state {} is malformed: {}", path.display(), e))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ArbitrageError> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode breaker state: {}", e)))?;
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, contents)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                ArbitrageError::ConfigError(format!("Failed to write breaker state {}: {}", path.display(), e))
+            })
+    }
+
+    pub fn active(&self, now: i64) -> impl Iterator<Item = (&String, &Trip)> {
+        self.trips.iter().filter(move |(_, trip)| trip.until > now)
+    }
+
+    // Clear one scope, or every trip when `scope` is `None`; returns how many were cleared
+    pub fn reset(&mut self, scope: Option<&str>) -> usize {
+        match scope {
+            Some(scope) => self.trips.remove(scope).map_or(0, |_| 1),
+            None => {
+                let cleared = self.trips.len();
+                self.trips.clear();
+                cleared
+            }
+        }
+    }
+}
+
+pub fn strategy_scope(strategy: &str) -> String {
+    format!("strategy:{}", strategy)
+}
+
+pub fn market_scope(market: &impl ToString) -> String {
+    format!("market:{}", market.to_string())
+}
+
+struct BreakerBook {
+    state: BreakerState,
+    consecutive_failures: HashMap<String, u32>,
+    // Modification time of the state file as of our last read or write
+    synced_at: Option<SystemTime>,
+}
+
+pub struct CircuitBreaker {
+    settings: SettingsHandle,
+    path: PathBuf,
+    alerts: AlertSender,
+    book: Mutex<BreakerBook>,
+}
+
+impl CircuitBreaker {
+    pub fn open(settings: SettingsHandle, path: &Path, alerts: AlertSender) -> Result<Self, ArbitrageError> {
+        let state = BreakerState::load(path)?;
+        for (scope, trip) in state.active(now_unix()) {
+            log::warn!("Circuit breaker still open for {} until {}: {}", scope, trip.until, trip.reason);
+        }
+
+        Ok(Self {
+            settings,
+            path: path.to_path_buf(),
+            alerts,
+            book: Mutex::new(BreakerBook {
+                state,
+                consecutive_failures: HashMap::new(),
+                synced_at: modified_at(path),
+            }),
+        })
+    }
+
+    // Reason the opportunity's strategy or one of its markets is paused, if any
+    pub fn blocked(&self, opportunity: &ArbitrageOpportunity) -> Option<String> {
+        let mut book = self.book.lock().unwrap();
+        self.sync(&mut book);
+
+        let now = now_unix();
+        let mut scopes = vec![strategy_scope(&opportunity.strategy), market_scope(&opportunity.source_market)];
+        scopes.extend(opportunity.route.iter().map(|step| market_scope(&step.market)));
+
+        scopes.iter().find_map(|scope| {
+            book.state
+                .trips
+                .get(scope)
+                .filter(|trip| trip.until > now)
+                .map(|trip| format!("{} paused: {}", scope, trip.reason))
+        })
+    }
+
+    pub fn record(&self, opportunity: &ArbitrageOpportunity, outcome: TradeOutcome) -> Result<(), ArbitrageError> {
+        let settings = self.settings.load();
+        let breaker = &settings.trading.risk.circuit_breaker;
+        let strategy = strategy_scope(&opportunity.strategy);
+        let market = market_scope(&opportunity.source_market);
+
+        let mut book = self.book.lock().unwrap();
+        self.sync(&mut book);
+
+        let trip = match outcome {
+            TradeOutcome::Failed => {
+                let failures = book.consecutive_failures.entry(strategy.clone()).or_default();
+                *failures += 1;
+                (*failures >= breaker.max_consecutive_failures).then(|| {
+                    (strategy.clone(), format!("{} consecutive failed sends", failures))
+                })
+            }
+            TradeOutcome::Landed { simulated_profit, realized_profit } => {
+                book.consecutive_failures.remove(&strategy);
+
+                let divergence = (simulated_profit - realized_profit).unsigned_abs() as f64
+                    / simulated_profit.unsigned_abs().max(1) as f64;
+
+                if realized_profit < 0 && realized_profit.unsigned_abs() > breaker.max_single_loss {
+                    Some((market, format!("realized loss of {} lamports", realized_profit.unsigned_abs())))
+                } else if divergence > breaker.max_profit_divergence {
+                    Some((
+                        market,
+                        format!(
+                            "realized profit {} diverged {:.0}% from simulated {}",
+                            realized_profit,
+                            divergence * 100.0,
+                            simulated_profit
+                        ),
+                    ))
+                } else {
+                    None
+                }
+            }
+        };
+
+        let (scope, reason) = match trip {
+            Some(trip) => trip,
+            None => return Ok(()),
+        };
+
+        let now = now_unix();
+        book.consecutive_failures.remove(&scope);
+        book.state.trips.insert(
+            scope.clone(),
+            Trip {
+                reason: reason.clone(),
+                tripped_at: now,
+                until: now + breaker.cooldown_secs as i64,
+            },
+        );
+        self.persist(&mut book)?;
+
+        self.alerts.send(Alert::new(
+            AlertSeverity::Critical,
+            "circuit_breaker",
+            format!("{} paused for {}s: {}", scope, breaker.cooldown_secs, reason),
+        ));
+
+        Ok(())
+    }
+
+    pub fn reset(&self, scope: Option<&str>) -> Result<usize, ArbitrageError> {
+        let mut book = self.book.lock().unwrap();
+        self.sync(&mut book);
+
+        let cleared = book.state.reset(scope);
+        match scope {
+            Some(scope) => {
+                book.consecutive_failures.remove(scope);
+            }
+            None => book.consecutive_failures.clear(),
+        }
+        self.persist(&mut book)?;
+
+        Ok(cleared)
+    }
+
+    pub fn state(&self) -> BreakerState {
+        let mut book = self.book.lock().unwrap();
+        self.sync(&mut book);
+        book.state.clone()
+    }
+
+    // Pick up resets made from the CLI while the bot is running
+    fn sync(&self, book: &mut BreakerBook) {
+        let modified = modified_at(&self.path);
+        if modified == book.synced_at {
+            return;
+        }
+
+        match BreakerState::load(&self.path) {
+            Ok(state) => {
+                for scope in book.state.trips.keys() {
+                    if !state.trips.contains_key(scope) {
+                        log::info!("Circuit breaker for {} was reset", scope);
+                    }
+                }
+                book.state = state;
+                book.synced_at = modified;
+            }
+            Err(e) => log::warn!("Ignoring breaker state file: {}", e),
+        }
+    }
+
+    fn persist(&self, book: &mut BreakerBook) -> Result<(), ArbitrageError> {
+        let now = now_unix();
+        book.state.trips.retain(|_, trip| trip.until > now);
+        book.state.save(&self.path)?;
+        book.synced_at = modified_at(&self.path);
+        Ok(())
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            config::Settings,
+            monitoring::alert_channel,
+            types::common::{Token, TokenPair},
+        },
+        solana_sdk::pubkey::Pubkey,
+    };
+
+    fn opportunity() -> ArbitrageOpportunity {
+        let token = Token {
+            address: Pubkey::new_unique(),
+            symbol: String::new(),
+            decimals: 9,
+        };
+        ArbitrageOpportunity {
+            source_market: Pubkey::new_unique(),
+            target_market: Pubkey::new_unique(),
+            token_pair: TokenPair {
+                base_token: token.clone(),
+                quote_token: token,
+            },
+            profit_percentage: 0.0,
+            required_amount: 1_000,
+            estimated_profit: 100,
+            route: Vec::new(),
+            timestamp: 0,
+            slot: 0,
+            strategy: "jit".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_consecutive_failures_trip_strategy_and_alert() {
+        let path = std::env::temp_dir().join(format!("arb-breaker-{}.json", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let mut settings = Settings::default();
+        settings.trading.risk.circuit_breaker.max_consecutive_failures = 2;
+        let (alerts, mut receiver) = alert_channel();
+        let breaker = CircuitBreaker::open(SettingsHandle::new(settings), &path, alerts).unwrap();
+        let trade = opportunity();
+
+        breaker.record(&trade, TradeOutcome::Failed).unwrap();
+        assert!(breaker.blocked(&trade).is_none());
+        breaker.record(&trade, TradeOutcome::Failed).unwrap();
+        assert!(breaker.blocked(&trade).unwrap().contains("strategy:jit"));
+        assert_eq!(receiver.try_recv().unwrap().severity, AlertSeverity::Critical);
+
+        // A reset written by another process is picked up
+        let mut state = BreakerState::load(&path).unwrap();
+        assert_eq!(state.reset(Some("strategy:jit")), 1);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        state.save(&path).unwrap();
+        assert!(breaker.blocked(&trade).is_none());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_profit_divergence_trips_market() {
+        let path = std::env::temp_dir().join(format!("arb-breaker-div-{}.json", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let (alerts, _receiver) = alert_channel();
+        let breaker = CircuitBreaker::open(SettingsHandle::new(Settings::default()), &path, alerts).unwrap();
+        let trade = opportunity();
+
+        breaker
+            .record(&trade, TradeOutcome::Landed { simulated_profit: 100, realized_profit: 95 })
+            .unwrap();
+        assert!(breaker.blocked(&trade).is_none());
+
+        breaker
+            .record(&trade, TradeOutcome::Landed { simulated_profit: 100, realized_profit: 10 })
+            .unwrap();
+        assert!(breaker.blocked(&trade).unwrap().contains("market:"));
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,460 @@
+mod circuit_breaker;
+
+pub use circuit_breaker::*;
+
+use {
+    crate::{
+        config::SettingsHandle,
+        strategies::RiskManager,
+        types::common::{ArbitrageError, ArbitrageOpportunity, TradeSide},
+    },
+    serde::{Deserialize, Serialize},
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::{HashMap, VecDeque},
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+const WINDOW_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskEntry {
+    pub timestamp: i64,
+    pub token: String,
+    // In raw units of the base asset, like `pnl`
+    pub notional: u64,
+    pub pnl: i64,
+}
+
+// Everything needed to rebuild the rolling window after a restart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RiskState {
+    pub entries: VecDeque<RiskEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskTotals {
+    pub volume: u64,
+    pub realized_pnl: i64,
+    pub drawdown: u64,
+}
+
+impl RiskState {
+    fn prune(&mut self, now: i64) {
+        while matches!(self.entries.front(), Some(entry) if now - entry.timestamp >= WINDOW_SECS) {
+            self.entries.pop_front();
+        }
+    }
+
+    // Totals over the window, for one token or overall when `token` is `None`
+    pub fn totals(&self, token: Option<&str>) -> RiskTotals {
+        let mut totals = RiskTotals::default();
+        let mut peak: i64 = 0;
+
+        for entry in self.entries.iter().filter(|entry| token.map_or(true, |token| entry.token == token)) {
+            totals.volume += entry.notional;
+            totals.realized_pnl += entry.pnl;
+            peak = peak.max(totals.realized_pnl);
+        }
+        totals.drawdown = (peak - totals.realized_pnl) as u64;
+
+        totals
+    }
+}
+
+struct RiskBook {
+    state: RiskState,
+    // Notional approved for trades that have not finished yet
+    reserved: HashMap<String, u64>,
+}
+
+// Rolling 24h volume and loss limits shared by every strategy, persisted to survive restarts
+pub struct DailyRiskManager {
+    settings: SettingsHandle,
+    path: PathBuf,
+    book: Mutex<RiskBook>,
+}
+
+impl DailyRiskManager {
+    pub fn open(settings: SettingsHandle, path: &Path) -> Result<Self, ArbitrageError> {
+        let mut state = if path.exists() {
+            let contents = std::fs::read_to_string(path).map_err(|e| {
+                ArbitrageError::ConfigError(format!("Failed to read risk state {}: {}", path.display(), e))
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                ArbitrageError::ConfigError(format!("Risk state {} is malformed: {}", path.display(), e))
+            })?
+        } else {
+            RiskState::default()
+        };
+        state.prune(now_unix());
+
+        let totals = state.totals(None);
+        log::info!(
+            "Risk window restored: volume {}, PnL {}, drawdown {}",
+            totals.volume,
+            totals.realized_pnl,
+            totals.drawdown
+        );
+
+        Ok(Self {
+            settings,
+            path: path.to_path_buf(),
+            book: Mutex::new(RiskBook {
+                state,
+                reserved: HashMap::new(),
+            }),
+        })
+    }
+
+    pub fn totals(&self, token: Option<&str>) -> RiskTotals {
+        let mut book = self.book.lock().unwrap();
+        book.state.prune(now_unix());
+        book.state.totals(token)
+    }
+
+    // The opportunity's notional in base-asset units, or why it cannot be valued
+    fn notional(&self, opportunity: &ArbitrageOpportunity) -> Result<u64, String> {
+        let base_mint = self.settings.load().trading.inventory.base_mint.clone();
+        let base_mint = Pubkey::from_str(&base_mint).map_err(|e| format!("Invalid base mint {}: {}", base_mint, e))?;
+
+        base_notional(opportunity, &base_mint).ok_or_else(|| {
+            format!(
+                "cannot value a {}/{} route in base asset {}",
+                opportunity.token_pair.base_token.address, opportunity.token_pair.quote_token.address, base_mint
+            )
+        })
+    }
+
+    // Reason the opportunity would breach a limit, if any
+    fn breach(&self, book: &RiskBook, opportunity: &ArbitrageOpportunity, notional: u64) -> Option<String> {
+        let settings = self.settings.load();
+        let risk = &settings.trading.risk;
+        let token = opportunity.token_pair.base_token.address.to_string();
+
+        // Volume already traded plus everything approved but still in flight
+        let reserved: u64 = book.reserved.values().sum();
+        let overall = book.state.totals(None);
+        if overall.volume + reserved + notional > risk.daily_volume_limit {
+            return Some(format!(
+                "24h volume {} + {} in flight + {} exceeds limit {}",
+                overall.volume, reserved, notional, risk.daily_volume_limit
+            ));
+        }
+
+        // Losses are budgeted as a fraction of the daily volume limit; assume the trade could
+        // lose its full slippage tolerance
+        let loss_budget = (risk.daily_volume_limit as f64 * risk.max_loss_threshold) as u64;
+        let worst_case_loss = (notional as f64 * risk.slippage_tolerance) as u64;
+
+        let per_token = book.state.totals(Some(&token));
+        for (scope, totals) in [("overall", &overall), (token.as_str(), &per_token)] {
+            if totals.drawdown + worst_case_loss > loss_budget {
+                return Some(format!(
+                    "{} drawdown {} + worst case {} exceeds loss budget {}",
+                    scope, totals.drawdown, worst_case_loss, loss_budget
+                ));
+            }
+        }
+
+        None
+    }
+
+    fn persist(&self, state: &RiskState) -> Result<(), ArbitrageError> {
+        let contents = serde_json::to_string(state)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to encode risk state: {}", e)))?;
+
+        // Write then rename so a crash never leaves a truncated file behind
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, contents)
+            .and_then(|_| std::fs::rename(&tmp, &self.path))
+            .map_err(|e| {
+                ArbitrageError::ConfigError(format!("Failed to write risk state {}: {}", self.path.display(), e))
+            })
+    }
+}
+
+impl RiskManager for DailyRiskManager {
+    fn check_risk_parameters(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
+        let mut book = self.book.lock().unwrap();
+        book.state.prune(now_unix());
+
+        // Limits are only meaningful in one unit, so a route that cannot be valued is never approved
+        let notional = match self.notional(opportunity) {
+            Ok(notional) => notional,
+            Err(reason) => {
+                log::warn!("Risk veto on {}: {}", opportunity.source_market, reason);
+                return Ok(false);
+            }
+        };
+        if let Some(reason) = self.breach(&book, opportunity, notional) {
+            log::warn!("Risk veto on {}: {}", opportunity.source_market, reason);
+            return Ok(false);
+        }
+
+        *book
+            .reserved
+            .entry(opportunity.token_pair.base_token.address.to_string())
+            .or_default() += notional;
+
+        Ok(true)
+    }
+
+    fn record_execution(&self, opportunity: &ArbitrageOpportunity, pnl: Option<i64>) -> Result<(), ArbitrageError> {
+        let token = opportunity.token_pair.base_token.address.to_string();
+        // Approved opportunities could be valued, so this only fails if the base mint changed since
+        let notional = self.notional(opportunity).unwrap_or(0);
+        let mut book = self.book.lock().unwrap();
+
+        if let Some(reserved) = book.reserved.get_mut(&token) {
+            *reserved = reserved.saturating_sub(notional);
+        }
+
+        // Trades that never reached the network release their reservation without counting as volume
+        let pnl = match pnl {
+            Some(pnl) => pnl,
+            None => return Ok(()),
+        };
+
+        let now = now_unix();
+        book.state.entries.push_back(RiskEntry {
+            timestamp: now,
+            token,
+            notional,
+            pnl,
+        });
+        book.state.prune(now);
+
+        self.persist(&book.state)
+    }
+}
+
+// Notional approved by `check_risk_parameters`. Dropping it unsettled releases the budget, so a trade that
+// errors, panics or is abandoned before it reaches the network never holds on to it.
+pub struct RiskReservation {
+    risk: Arc<dyn RiskManager>,
+    opportunity: ArbitrageOpportunity,
+    settled: bool,
+}
+
+impl RiskReservation {
+    // `None` when the opportunity would breach a limit
+    pub fn acquire(
+        risk: &Arc<dyn RiskManager>,
+        opportunity: &ArbitrageOpportunity,
+    ) -> Result<Option<Self>, ArbitrageError> {
+        if !risk.check_risk_parameters(opportunity)? {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            risk: risk.clone(),
+            opportunity: opportunity.clone(),
+            settled: false,
+        }))
+    }
+
+    // Record what the trade made or lost once it has reached the network
+    pub fn settle(mut self, pnl: i64) -> Result<(), ArbitrageError> {
+        self.settled = true;
+        self.risk.record_execution(&self.opportunity, Some(pnl))
+    }
+}
+
+impl Drop for RiskReservation {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+        if let Err(e) = self.risk.record_execution(&self.opportunity, None) {
+            log::error!("Failed to release risk reservation on {}: {}", self.opportunity.source_market, e);
+        }
+    }
+}
+
+// Notional in raw units of the base asset (`trading.inventory.base_mint`), the unit realized PnL is measured in, so
+// volume and drawdown add up across tokens with different decimals. A route spends its first leg's input: the quote
+// token for a buy, the base token for a sell. `None` unless that token or the other side of its pair is the base
+// asset; the first leg's price converts between them.
+pub fn base_notional(opportunity: &ArbitrageOpportunity, base_mint: &Pubkey) -> Option<u64> {
+    let pair = &opportunity.token_pair;
+    let amount = opportunity.required_amount as f64;
+
+    // Flash loan legs use a placeholder market and carry no price
+    let first = opportunity.route.iter().find(|step| step.market != Pubkey::default());
+    let (spent, other) = match first.map(|step| step.side) {
+        Some(TradeSide::Buy) => (&pair.quote_token, &pair.base_token),
+        Some(TradeSide::Sell) | None => (&pair.base_token, &pair.quote_token),
+    };
+
+    if spent.address == *base_mint {
+        return Some(opportunity.required_amount);
+    }
+    let step = first?;
+    if other.address != *base_mint || !step.price.is_finite() || step.price <= 0.0 {
+        return None;
+    }
+
+    // Prices are quote per base in raw units
+    let value = match step.side {
+        TradeSide::Buy => amount / step.price,
+        TradeSide::Sell => amount * step.price,
+    };
+    Some(value as u64)
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            config::Settings,
+            types::common::{Token, TokenPair, TradeStep},
+        },
+    };
+
+    fn token(address: Pubkey, decimals: u8) -> Token {
+        Token {
+            address,
+            symbol: String::new(),
+            decimals,
+        }
+    }
+
+    fn base_mint() -> Pubkey {
+        Pubkey::from_str(&Settings::default().trading.inventory.base_mint).unwrap()
+    }
+
+    // A route whose first leg trades `base` against `quote` at `price`, spending `amount`
+    fn route(base: Token, quote: Token, side: TradeSide, amount: u64, price: f64) -> ArbitrageOpportunity {
+        ArbitrageOpportunity {
+            source_market: Pubkey::new_unique(),
+            target_market: Pubkey::new_unique(),
+            token_pair: TokenPair {
+                base_token: base,
+                quote_token: quote,
+            },
+            profit_percentage: 0.0,
+            required_amount: amount,
+            estimated_profit: 0,
+            route: vec![TradeStep {
+                market: Pubkey::new_unique(),
+                side,
+                amount,
+                price,
+            }],
+            timestamp: 0,
+            slot: 0,
+            strategy: "direct".to_string(),
+        }
+    }
+
+    // Buys `token_address` with `amount` of the base asset
+    fn opportunity(token_address: Pubkey, amount: u64) -> ArbitrageOpportunity {
+        route(token(token_address, 6), token(base_mint(), 9), TradeSide::Buy, amount, 1.0)
+    }
+
+    fn manager(name: &str) -> (DailyRiskManager, PathBuf) {
+        let mut settings = Settings::default();
+        settings.trading.risk.daily_volume_limit = 1_000;
+        settings.trading.risk.max_loss_threshold = 0.1;
+        settings.trading.risk.slippage_tolerance = 0.0;
+        settings.trading.execution.max_position_size = 1_000;
+
+        let path = std::env::temp_dir().join(format!("arb-risk-{}-{}.json", name, std::process::id()));
+        std::fs::remove_file(&path).ok();
+        (DailyRiskManager::open(SettingsHandle::new(settings), &path).unwrap(), path)
+    }
+
+    #[test]
+    fn test_notional_is_valued_in_the_base_asset() {
+        let usdc = token(Pubkey::new_unique(), 6);
+        let sol = token(base_mint(), 9);
+        let value = |opportunity: ArbitrageOpportunity| base_notional(&opportunity, &base_mint());
+
+        // Spending the base asset itself
+        assert_eq!(value(route(sol.clone(), usdc.clone(), TradeSide::Sell, 2_000_000_000, 0.15)), Some(2_000_000_000));
+        // 1 USDC spent buying SOL at 150 USDC per SOL, i.e. 0.15 raw USDC per lamport
+        assert_eq!(value(route(sol.clone(), usdc.clone(), TradeSide::Buy, 1_000_000, 0.15)), Some(6_666_666));
+        // 1 USDC sold for SOL at 1/150 SOL per USDC in raw units
+        assert_eq!(value(route(usdc.clone(), sol, TradeSide::Sell, 1_000_000, 1e9 / 150e6)), Some(6_666_666));
+        // Neither side is the base asset
+        let other = token(Pubkey::new_unique(), 6);
+        assert_eq!(value(route(usdc, other, TradeSide::Sell, 1_000_000, 1.0)), None);
+    }
+
+    #[test]
+    fn test_route_that_cannot_be_valued_is_vetoed() {
+        let (risk, path) = manager("unvalued");
+        let trade = route(token(Pubkey::new_unique(), 6), token(Pubkey::new_unique(), 6), TradeSide::Sell, 1, 1.0);
+
+        assert!(!risk.check_risk_parameters(&trade).unwrap());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_volume_limit_counts_in_flight_trades() {
+        let (risk, path) = manager("volume");
+        let token = Pubkey::new_unique();
+
+        assert!(risk.check_risk_parameters(&opportunity(token, 600)).unwrap());
+        assert!(!risk.check_risk_parameters(&opportunity(token, 600)).unwrap());
+
+        // A trade that never landed frees its budget
+        risk.record_execution(&opportunity(token, 600), None).unwrap();
+        assert!(risk.check_risk_parameters(&opportunity(token, 600)).unwrap());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_unsettled_reservation_releases_budget() {
+        let (risk, path) = manager("reservation");
+        let risk: Arc<dyn RiskManager> = Arc::new(risk);
+        let trade = opportunity(Pubkey::new_unique(), 600);
+
+        let reservation = RiskReservation::acquire(&risk, &trade).unwrap().unwrap();
+        assert!(RiskReservation::acquire(&risk, &trade).unwrap().is_none());
+
+        // An abandoned trade gives its budget back
+        drop(reservation);
+        let reservation = RiskReservation::acquire(&risk, &trade).unwrap().unwrap();
+
+        // A failed send still costs its fee
+        reservation.settle(-5_000).unwrap();
+        assert!(RiskReservation::acquire(&risk, &trade).unwrap().is_none());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_drawdown_veto_survives_restart() {
+        let (risk, path) = manager("drawdown");
+        let token = Pubkey::new_unique();
+
+        let trade = opportunity(token, 100);
+        assert!(risk.check_risk_parameters(&trade).unwrap());
+        risk.record_execution(&trade, Some(50)).unwrap();
+        assert!(risk.check_risk_parameters(&trade).unwrap());
+        risk.record_execution(&trade, Some(-150)).unwrap();
+
+        assert_eq!(risk.totals(None), RiskTotals { volume: 200, realized_pnl: -100, drawdown: 150 });
+        assert!(!risk.check_risk_parameters(&trade).unwrap());
+
+        let settings = risk.settings.clone();
+        drop(risk);
+        let restored = DailyRiskManager::open(settings, &path).unwrap();
+        assert_eq!(restored.totals(Some(&token.to_string())).volume, 200);
+        assert!(!restored.check_risk_parameters(&trade).unwrap());
+        std::fs::remove_file(path).ok();
+    }
+}
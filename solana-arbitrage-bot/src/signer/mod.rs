@@ -0,0 +1,12 @@
+mod protocol;
+mod remote;
+mod server;
+
+pub use protocol::*;
+pub use remote::*;
+pub use server::*;
+
+use {solana_sdk::signer::Signer, std::sync::Arc};
+
+// Signing backend shared by the engine and transaction builder
+pub type SharedSigner = Arc<dyn Signer + Send + Sync>;
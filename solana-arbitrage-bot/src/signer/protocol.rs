@@ -0,0 +1,114 @@
+use {
+    crate::types::common::ArbitrageError,
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
+    hmac::{Hmac, Mac},
+    serde::{Deserialize, Serialize},
+    sha2::Sha256,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Requests older or newer than this are rejected to limit replay
+pub const MAX_CLOCK_SKEW_SECS: i64 = 30;
+
+// One JSON object per line in each direction over the Unix socket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignerRequest {
+    Pubkey {
+        nonce: u64,
+        timestamp: i64,
+        mac: String,
+    },
+    Sign {
+        nonce: u64,
+        timestamp: i64,
+        message: String,
+        mac: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignerResponse {
+    Pubkey { pubkey: String },
+    Signature { signature: String },
+    Error { reason: String },
+}
+
+impl SignerRequest {
+    pub fn pubkey(secret: &[u8], nonce: u64, timestamp: i64) -> Self {
+        let mac = compute_mac(secret, "pubkey", nonce, timestamp, &[]);
+        SignerRequest::Pubkey { nonce, timestamp, mac }
+    }
+
+    pub fn sign(secret: &[u8], nonce: u64, timestamp: i64, message: &[u8]) -> Self {
+        let mac = compute_mac(secret, "sign", nonce, timestamp, message);
+        SignerRequest::Sign {
+            nonce,
+            timestamp,
+            message: BASE64.encode(message),
+            mac,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        match self {
+            SignerRequest::Pubkey { nonce, .. } | SignerRequest::Sign { nonce, .. } => *nonce,
+        }
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        match self {
+            SignerRequest::Pubkey { timestamp, .. } | SignerRequest::Sign { timestamp, .. } => *timestamp,
+        }
+    }
+
+    // Verify the MAC and return the decoded message bytes, if any
+    pub fn authenticate(&self, secret: &[u8]) -> Result<Vec<u8>, ArbitrageError> {
+        let (kind, nonce, timestamp, message, mac) = match self {
+            SignerRequest::Pubkey { nonce, timestamp, mac } => ("pubkey", *nonce, *timestamp, Vec::new(), mac),
+            SignerRequest::Sign { nonce, timestamp, message, mac } => {
+                let message = BASE64.decode(message).map_err(|e| {
+                    ArbitrageError::SecurityViolation(format!("Message is not base64: {}", e))
+                })?;
+                ("sign", *nonce, *timestamp, message, mac)
+            }
+        };
+
+        let mac = BASE64
+            .decode(mac)
+            .map_err(|_| ArbitrageError::SecurityViolation("MAC is not base64".to_string()))?;
+        mac_for(secret, kind, nonce, timestamp, &message)
+            .verify_slice(&mac)
+            .map_err(|_| ArbitrageError::SecurityViolation("Request authentication failed".to_string()))?;
+
+        Ok(message)
+    }
+}
+
+fn mac_for(secret: &[u8], kind: &str, nonce: u64, timestamp: i64, message: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}:{}:{}:", kind, nonce, timestamp).as_bytes());
+    mac.update(message);
+    mac
+}
+
+fn compute_mac(secret: &[u8], kind: &str, nonce: u64, timestamp: i64, message: &[u8]) -> String {
+    BASE64.encode(mac_for(secret, kind, nonce, timestamp, message).finalize().into_bytes())
+}
+
+pub fn read_auth_secret(env_var: &str) -> Result<Vec<u8>, ArbitrageError> {
+    let secret = std::env::var(env_var).map_err(|_| {
+        ArbitrageError::ConfigError(format!("Signer auth secret {} is not set", env_var))
+    })?;
+
+    if secret.len() < 32 {
+        return Err(ArbitrageError::ConfigError(format!(
+            "Signer auth secret {} must be at least 32 characters",
+            env_var
+        )));
+    }
+
+    Ok(secret.into_bytes())
+}
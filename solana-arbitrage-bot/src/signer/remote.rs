@@ -0,0 +1,166 @@
+use {
+    super::{read_auth_secret, SignerRequest, SignerResponse},
+    crate::{config::SignerSettings, types::common::ArbitrageError},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::{Signer, SignerError},
+    },
+    std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixStream,
+        path::PathBuf,
+        str::FromStr,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+// Signs through a separate signer process reached over a Unix socket
+pub struct RemoteSigner {
+    socket_path: PathBuf,
+    secret: Vec<u8>,
+    timeout: Duration,
+    pubkey: Pubkey,
+    next_nonce: AtomicU64,
+    connection: Mutex<Option<BufReader<UnixStream>>>,
+}
+
+impl RemoteSigner {
+    pub fn connect(settings: &SignerSettings) -> Result<Self, ArbitrageError> {
+        let secret = read_auth_secret(&settings.auth_secret_env)?;
+        Self::with_secret(PathBuf::from(&settings.socket_path), secret, settings.timeout_ms)
+    }
+
+    pub fn with_secret(socket_path: PathBuf, secret: Vec<u8>, timeout_ms: u64) -> Result<Self, ArbitrageError> {
+        let mut signer = Self {
+            socket_path,
+            secret,
+            timeout: Duration::from_millis(timeout_ms),
+            pubkey: Pubkey::default(),
+            // Seed from the clock so nonces keep increasing across restarts
+            next_nonce: AtomicU64::new(now_unix() as u64 * 1_000_000),
+            connection: Mutex::new(None),
+        };
+
+        let request = SignerRequest::pubkey(&signer.secret, signer.nonce(), now_unix());
+        signer.pubkey = match signer.call(&request)? {
+            SignerResponse::Pubkey { pubkey } => Pubkey::from_str(&pubkey).map_err(|e| {
+                ArbitrageError::SecurityViolation(format!("Signer returned an invalid pubkey: {}", e))
+            })?,
+            other => return Err(unexpected_response(other)),
+        };
+
+        Ok(signer)
+    }
+
+    // Round trip to the signer; fails when it is down or rejects our secret
+    pub fn ping(&self) -> Result<(), ArbitrageError> {
+        let request = SignerRequest::pubkey(&self.secret, self.nonce(), now_unix());
+        match self.call(&request)? {
+            SignerResponse::Pubkey { .. } => Ok(()),
+            other => Err(unexpected_response(other)),
+        }
+    }
+
+    fn nonce(&self) -> u64 {
+        self.next_nonce.fetch_add(1, Ordering::SeqCst)
+    }
+
+    fn call(&self, request: &SignerRequest) -> Result<SignerResponse, ArbitrageError> {
+        let mut connection = self.connection.lock().unwrap();
+
+        // Reconnect lazily; a broken connection is dropped and retried once
+        for attempt in 0..2 {
+            if connection.is_none() {
+                let stream = UnixStream::connect(&self.socket_path).map_err(|e| {
+                    ArbitrageError::NetworkError(format!(
+                        "Failed to connect to signer at {}: {}",
+                        self.socket_path.display(),
+                        e
+                    ))
+                })?;
+                stream.set_read_timeout(Some(self.timeout)).ok();
+                stream.set_write_timeout(Some(self.timeout)).ok();
+                *connection = Some(BufReader::new(stream));
+            }
+
+            match exchange(connection.as_mut().unwrap(), request) {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt == 0 => {
+                    log::warn!("Signer connection failed, reconnecting: {}", e);
+                    *connection = None;
+                }
+                Err(e) => {
+                    *connection = None;
+                    return Err(e);
+                }
+            }
+        }
+
+        unreachable!("signer call loop always returns")
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        let request = SignerRequest::sign(&self.secret, self.nonce(), now_unix(), message);
+
+        match self.call(&request).map_err(|e| SignerError::Connection(e.to_string()))? {
+            SignerResponse::Signature { signature } => Signature::from_str(&signature)
+                .map_err(|e| SignerError::Protocol(format!("Invalid signature from signer: {}", e))),
+            SignerResponse::Error { reason } => Err(SignerError::Custom(reason)),
+            other => Err(SignerError::Protocol(unexpected_response(other).to_string())),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+fn exchange(
+    connection: &mut BufReader<UnixStream>,
+    request: &SignerRequest,
+) -> Result<SignerResponse, ArbitrageError> {
+    let mut line = serde_json::to_string(request)
+        .map_err(|e| ArbitrageError::SecurityViolation(format!("Failed to encode signer request: {}", e)))?;
+    line.push('\n');
+
+    connection
+        .get_mut()
+        .write_all(line.as_bytes())
+        .map_err(|e| ArbitrageError::NetworkError(format!("Failed to write to signer: {}", e)))?;
+
+    let mut response = String::new();
+    let read = connection
+        .read_line(&mut response)
+        .map_err(|e| ArbitrageError::NetworkError(format!("Failed to read from signer: {}", e)))?;
+    if read == 0 {
+        return Err(ArbitrageError::NetworkError("Signer closed the connection".to_string()));
+    }
+
+    serde_json::from_str(&response)
+        .map_err(|e| ArbitrageError::SecurityViolation(format!("Malformed signer response: {}", e)))
+}
+
+fn unexpected_response(response: SignerResponse) -> ArbitrageError {
+    match response {
+        SignerResponse::Error { reason } => ArbitrageError::SecurityViolation(reason),
+        other => ArbitrageError::SecurityViolation(format!("Unexpected signer response: {:?}", other)),
+    }
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
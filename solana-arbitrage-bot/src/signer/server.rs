@@ -0,0 +1,448 @@
+use {
+    super::{SignerRequest, SignerResponse, MAX_CLOCK_SKEW_SECS},
+    crate::{config::SignerSettings, types::common::ArbitrageError},
+    solana_sdk::{
+        message::VersionedMessage,
+        pubkey,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction::SystemInstruction,
+        system_program,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        path::Path,
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::{UnixListener, UnixStream},
+    },
+};
+
+const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// SPL token instruction tags the bot issues; every other token instruction is refused
+const TOKEN_TRANSFER: u8 = 3;
+const TOKEN_CLOSE_ACCOUNT: u8 = 9;
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+const TOKEN_SYNC_NATIVE: u8 = 17;
+
+// Limits enforced by the signer regardless of what the bot asks for
+#[derive(Debug, Clone)]
+pub struct SigningPolicy {
+    pub allowed_programs: HashSet<Pubkey>,
+    pub max_transfer_lamports: u64,
+    pub max_token_amount: u64,
+}
+
+impl SigningPolicy {
+    pub fn from_settings(settings: &SignerSettings) -> Result<Self, ArbitrageError> {
+        let allowed_programs = settings
+            .allowed_programs
+            .iter()
+            .map(|program| {
+                Pubkey::from_str(program).map_err(|e| {
+                    ArbitrageError::ConfigError(format!("Invalid allowed program {}: {}", program, e))
+                })
+            })
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        Ok(Self {
+            allowed_programs,
+            max_transfer_lamports: settings.max_transfer_lamports,
+            max_token_amount: settings.max_token_amount,
+        })
+    }
+
+    pub fn check(&self, message: &[u8]) -> Result<(), String> {
+        let message: VersionedMessage =
+            bincode::deserialize(message).map_err(|e| format!("message could not be decoded: {}", e))?;
+        let account_keys = message.static_account_keys();
+
+        let mut lamports: u64 = 0;
+        let mut tokens: u64 = 0;
+
+        for instruction in message.instructions() {
+            // Programs loaded from lookup tables cannot be checked against the allowlist
+            let program_id = account_keys
+                .get(instruction.program_id_index as usize)
+                .ok_or_else(|| "program id is not a static account key".to_string())?;
+
+            if !self.allowed_programs.contains(program_id) {
+                return Err(format!("program {} is not allowed", program_id));
+            }
+
+            // System and token instructions are refused unless listed here: the rest can hand an account,
+            // a delegate or an authority to someone else without moving anything the caps would see
+            if *program_id == system_program::id() {
+                match bincode::deserialize::<SystemInstruction>(&instruction.data) {
+                    Ok(SystemInstruction::Transfer { lamports: amount })
+                    | Ok(SystemInstruction::TransferWithSeed { lamports: amount, .. }) => {
+                        lamports = lamports.saturating_add(amount);
+                    }
+                    Ok(other) => return Err(format!("system instruction {:?} is not allowed", other)),
+                    Err(e) => return Err(format!("system instruction could not be decoded: {}", e)),
+                }
+            } else if *program_id == TOKEN_PROGRAM_ID || *program_id == TOKEN_2022_PROGRAM_ID {
+                match instruction.data.first() {
+                    Some(&TOKEN_TRANSFER) | Some(&TOKEN_TRANSFER_CHECKED) => {
+                        let amount = token_transfer_amount(&instruction.data)
+                            .ok_or_else(|| "token transfer is truncated".to_string())?;
+                        tokens = tokens.saturating_add(amount);
+                    }
+                    Some(&TOKEN_SYNC_NATIVE) => {}
+                    // Rent may only go back to the account's own authority
+                    Some(&TOKEN_CLOSE_ACCOUNT) => {
+                        let key = |position: usize| {
+                            instruction
+                                .accounts
+                                .get(position)
+                                .and_then(|index| account_keys.get(*index as usize))
+                        };
+                        match (key(1), key(2)) {
+                            (Some(destination), Some(owner)) if destination == owner => {}
+                            _ => return Err("token account may only be closed to its owner".to_string()),
+                        }
+                    }
+                    Some(tag) => return Err(format!("token instruction {} is not allowed", tag)),
+                    None => return Err("token instruction has no data".to_string()),
+                }
+            }
+        }
+
+        if lamports > self.max_transfer_lamports {
+            return Err(format!(
+                "transfers {} lamports, limit is {}",
+                lamports, self.max_transfer_lamports
+            ));
+        }
+        if tokens > self.max_token_amount {
+            return Err(format!(
+                "transfers {} token units, limit is {}",
+                tokens, self.max_token_amount
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn token_transfer_amount(data: &[u8]) -> Option<u64> {
+    match data.first() {
+        Some(&TOKEN_TRANSFER) | Some(&TOKEN_TRANSFER_CHECKED) => data
+            .get(1..9)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap())),
+        _ => None,
+    }
+}
+
+// Holds the key and answers authenticated signing requests over a Unix socket
+pub struct SignerServer {
+    keypair: Keypair,
+    secret: Vec<u8>,
+    policy: SigningPolicy,
+    // Nonces seen within the clock skew window, keyed to their timestamp
+    seen_nonces: Mutex<HashMap<u64, i64>>,
+}
+
+impl SignerServer {
+    pub fn new(keypair: Keypair, secret: Vec<u8>, policy: SigningPolicy) -> Self {
+        Self {
+            keypair,
+            secret,
+            policy,
+            seen_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn bind(socket_path: &Path) -> Result<UnixListener, ArbitrageError> {
+        // A socket left behind by a previous run would make bind fail
+        if socket_path.exists() {
+            std::fs::remove_file(socket_path).map_err(|e| {
+                ArbitrageError::ConfigError(format!("Failed to remove stale socket {}: {}", socket_path.display(), e))
+            })?;
+        }
+
+        let listener = UnixListener::bind(socket_path).map_err(|e| {
+            ArbitrageError::NetworkError(format!("Failed to bind signer socket {}: {}", socket_path.display(), e))
+        })?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600)).map_err(|e| {
+                ArbitrageError::ConfigError(format!("Failed to restrict {}: {}", socket_path.display(), e))
+            })?;
+        }
+
+        Ok(listener)
+    }
+
+    pub async fn serve(self: Arc<Self>, listener: UnixListener) -> Result<(), ArbitrageError> {
+        log::info!("Signer serving {}", self.keypair.pubkey());
+
+        loop {
+            let (stream, _) = listener
+                .accept()
+                .await
+                .map_err(|e| ArbitrageError::NetworkError(format!("Signer accept failed: {}", e)))?;
+
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.handle_connection(stream).await {
+                    log::warn!("Signer connection closed: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(&self, stream: UnixStream) -> std::io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Some(line) = lines.next_line().await? {
+            let response = match serde_json::from_str::<SignerRequest>(&line) {
+                Ok(request) => self.handle(&request),
+                Err(e) => SignerResponse::Error {
+                    reason: format!("malformed request: {}", e),
+                },
+            };
+
+            let mut encoded = serde_json::to_string(&response).unwrap_or_default();
+            encoded.push('\n');
+            writer.write_all(encoded.as_bytes()).await?;
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(&self, request: &SignerRequest) -> SignerResponse {
+        match self.authorize(request) {
+            Ok(message) => match request {
+                SignerRequest::Pubkey { .. } => SignerResponse::Pubkey {
+                    pubkey: self.keypair.pubkey().to_string(),
+                },
+                SignerRequest::Sign { .. } => match self.policy.check(&message) {
+                    Ok(()) => SignerResponse::Signature {
+                        signature: self.keypair.sign_message(&message).to_string(),
+                    },
+                    Err(reason) => {
+                        log::warn!("Refused to sign: {}", reason);
+                        SignerResponse::Error { reason }
+                    }
+                },
+            },
+            Err(e) => {
+                log::warn!("Rejected signer request: {}", e);
+                SignerResponse::Error { reason: e.to_string() }
+            }
+        }
+    }
+
+    fn authorize(&self, request: &SignerRequest) -> Result<Vec<u8>, ArbitrageError> {
+        let message = request.authenticate(&self.secret)?;
+
+        let now = now_unix();
+        if (now - request.timestamp()).abs() > MAX_CLOCK_SKEW_SECS {
+            return Err(ArbitrageError::SecurityViolation("Request timestamp outside the allowed window".to_string()));
+        }
+
+        // Only authenticated requests reach the replay table, so it cannot be flooded by strangers
+        let mut seen = self.seen_nonces.lock().unwrap();
+        seen.retain(|_, timestamp| now - *timestamp <= MAX_CLOCK_SKEW_SECS);
+        if seen.insert(request.nonce(), request.timestamp()).is_some() {
+            return Err(ArbitrageError::SecurityViolation(format!("Replayed nonce {}", request.nonce())));
+        }
+
+        Ok(message)
+    }
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::signer::RemoteSigner,
+        solana_sdk::{
+            hash::Hash,
+            instruction::{AccountMeta, Instruction},
+            system_instruction,
+            transaction::Transaction,
+        },
+    };
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn policy() -> SigningPolicy {
+        SigningPolicy {
+            allowed_programs: [system_program::id()].into_iter().collect(),
+            max_transfer_lamports: 1_000,
+            max_token_amount: 0,
+        }
+    }
+
+    // Run a local stand-in signer on a temporary socket
+    fn start_server(name: &str, keypair: Keypair) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("arb-signer-{}-{}.sock", name, std::process::id()));
+        let listener = SignerServer::bind(&path).unwrap();
+        let server = Arc::new(SignerServer::new(keypair, SECRET.to_vec(), policy()));
+        tokio::spawn(server.serve(listener));
+        path
+    }
+
+    fn sign(signer: &RemoteSigner, instruction: Instruction) -> Result<Transaction, String> {
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&signer.pubkey()));
+        transaction
+            .try_sign(&[signer], Hash::default())
+            .map(|_| transaction)
+            .map_err(|e| e.to_string())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remote_signer_enforces_policy() {
+        let keypair = Keypair::new();
+        let expected = keypair.pubkey();
+        let path = start_server("policy", keypair);
+
+        tokio::task::spawn_blocking(move || {
+            let signer = RemoteSigner::with_secret(path.clone(), SECRET.to_vec(), 2_000).unwrap();
+            assert_eq!(signer.pubkey(), expected);
+
+            let to = Pubkey::new_unique();
+            let transaction = sign(&signer, system_instruction::transfer(&expected, &to, 500)).unwrap();
+            assert!(transaction.verify().is_ok());
+
+            // Over the transfer cap
+            assert!(sign(&signer, system_instruction::transfer(&expected, &to, 5_000)).is_err());
+
+            // Program outside the allowlist
+            let foreign = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+            assert!(sign(&signer, foreign).is_err());
+
+            std::fs::remove_file(path).ok();
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_wrong_secret_is_rejected() {
+        let path = start_server("secret", Keypair::new());
+
+        tokio::task::spawn_blocking(move || {
+            let wrong = b"ffffffffffffffffffffffffffffffff".to_vec();
+            assert!(RemoteSigner::with_secret(path.clone(), wrong, 2_000).is_err());
+            std::fs::remove_file(path).ok();
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_replayed_request_is_rejected() {
+        let server = SignerServer::new(Keypair::new(), SECRET.to_vec(), policy());
+        let request = SignerRequest::pubkey(SECRET, 7, now_unix());
+
+        assert!(matches!(server.handle(&request), SignerResponse::Pubkey { .. }));
+        assert!(matches!(server.handle(&request), SignerResponse::Error { .. }));
+    }
+
+    fn token_policy() -> SigningPolicy {
+        SigningPolicy {
+            allowed_programs: [system_program::id(), TOKEN_PROGRAM_ID].into_iter().collect(),
+            max_transfer_lamports: 1_000,
+            max_token_amount: 1_000,
+        }
+    }
+
+    fn message(instruction: Instruction, payer: &Pubkey) -> Vec<u8> {
+        let message = solana_sdk::message::Message::new(&[instruction], Some(payer));
+        bincode::serialize(&VersionedMessage::Legacy(message)).unwrap()
+    }
+
+    #[test]
+    fn test_token_approve_is_refused() {
+        let owner = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+
+        // Approve { amount } with accounts [source, delegate, owner]
+        let mut data = vec![4];
+        data.extend_from_slice(&1u64.to_le_bytes());
+        let approve = Instruction::new_with_bytes(
+            TOKEN_PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(account, false),
+                AccountMeta::new_readonly(attacker, false),
+                AccountMeta::new_readonly(owner, true),
+            ],
+        );
+        assert!(token_policy().check(&message(approve, &owner)).is_err());
+    }
+
+    #[test]
+    fn test_token_set_authority_is_refused() {
+        let owner = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+
+        // SetAuthority { AccountOwner, Some(attacker) } with accounts [account, current authority]
+        let mut data = vec![6, 2, 1];
+        data.extend_from_slice(attacker.as_ref());
+        let set_authority = Instruction::new_with_bytes(
+            TOKEN_PROGRAM_ID,
+            &data,
+            vec![AccountMeta::new(account, false), AccountMeta::new_readonly(owner, true)],
+        );
+        assert!(token_policy().check(&message(set_authority, &owner)).is_err());
+    }
+
+    #[test]
+    fn test_only_listed_system_and_token_instructions_pass() {
+        let owner = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let policy = token_policy();
+
+        let transfer = system_instruction::transfer(&owner, &Pubkey::new_unique(), 500);
+        assert!(policy.check(&message(transfer, &owner)).is_ok());
+        let assign = system_instruction::assign(&owner, &Pubkey::new_unique());
+        assert!(policy.check(&message(assign, &owner)).is_err());
+
+        let close = |destination: Pubkey| {
+            Instruction::new_with_bytes(
+                TOKEN_PROGRAM_ID,
+                &[TOKEN_CLOSE_ACCOUNT],
+                vec![
+                    AccountMeta::new(account, false),
+                    AccountMeta::new(destination, false),
+                    AccountMeta::new_readonly(owner, true),
+                ],
+            )
+        };
+        assert!(policy.check(&message(close(owner), &owner)).is_ok());
+        assert!(policy.check(&message(close(Pubkey::new_unique()), &owner)).is_err());
+
+        let burn = Instruction::new_with_bytes(TOKEN_PROGRAM_ID, &[8, 1, 0, 0, 0, 0, 0, 0, 0], vec![]);
+        assert!(policy.check(&message(burn, &owner)).is_err());
+    }
+
+    #[test]
+    fn test_token_transfer_amount_is_capped() {
+        let mut data = vec![TOKEN_TRANSFER];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        assert_eq!(token_transfer_amount(&data), Some(42));
+        assert_eq!(token_transfer_amount(&[7]), None);
+    }
+}
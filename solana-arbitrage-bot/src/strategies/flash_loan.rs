@@ -106,6 +106,8 @@ impl FlashLoanStrategy {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            slot: market1_state.slot.min(market2_state.slot),
+            strategy: "flash_loan".to_string(),
         };
 
         Ok(Some(opportunity))
@@ -307,6 +309,11 @@ impl ArbitrageStrategy for FlashLoanStrategy {
         "Flash Loan Strategy"
     }
 
+    fn refresh(&mut self, settings: Arc<Settings>, market_states: Arc<Vec<MarketState>>) {
+        self.settings = settings;
+        self.market_states = market_states;
+    }
+
     fn analyze(&self, markets: &[Pubkey]) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
         self.find_flash_loan_opportunities(markets)
     }
@@ -316,15 +323,10 @@ impl ArbitrageStrategy for FlashLoanStrategy {
         unimplemented!("Flash loan execution not implemented")
     }
 
-    fn validate(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
+    fn validate(&self, opportunity: &ArbitrageOpportunity, current_slot: u64) -> Result<bool, ArbitrageError> {
         // Check if opportunity is still fresh
-        if SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
-            - opportunity.timestamp
-            > 3 // Flash loan opportunities need to be very fresh
-        {
+        let max_age = self.settings.trading.execution.max_opportunity_age_slots.flash_loan;
+        if current_slot.saturating_sub(opportunity.slot) > max_age {
             return Ok(false);
         }
 
@@ -333,6 +335,17 @@ impl ArbitrageStrategy for FlashLoanStrategy {
         let market2_state = self.get_market_state(&opportunity.target_market)?;
 
         // Recheck market conditions
-        self.are_markets_suitable(market1_state, market2_state)
+        if !self.are_markets_suitable(market1_state, market2_state)? {
+            return Ok(false);
+        }
+
+        // Re-quote the route against the latest market state
+        let (profit_percentage, _) = self.calculate_flash_loan_profit(
+            market1_state,
+            market2_state,
+            opportunity.required_amount,
+        )?;
+
+        Ok(profit_percentage >= self.settings.trading.execution.min_profit_threshold)
     }
 }
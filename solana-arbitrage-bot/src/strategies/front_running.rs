@@ -106,6 +106,8 @@ impl FrontRunningStrategy {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            slot: market_state.slot,
+            strategy: "front_running".to_string(),
         };
 
         Ok(Some(opportunity))
@@ -316,6 +318,11 @@ impl ArbitrageStrategy for FrontRunningStrategy {
         "Front Running Strategy"
     }
 
+    fn refresh(&mut self, settings: Arc<Settings>, market_states: Arc<Vec<MarketState>>) {
+        self.settings = settings;
+        self.market_states = market_states;
+    }
+
     fn analyze(&self, markets: &[Pubkey]) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
         self.find_front_running_opportunities(markets)
     }
@@ -325,15 +332,10 @@ impl ArbitrageStrategy for FrontRunningStrategy {
         unimplemented!("Front running execution not implemented")
     }
 
-    fn validate(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
+    fn validate(&self, opportunity: &ArbitrageOpportunity, current_slot: u64) -> Result<bool, ArbitrageError> {
         // Front-running opportunities need to be extremely fresh
-        if SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
-            - opportunity.timestamp
-            > 1
-        {
+        let max_age = self.settings.trading.execution.max_opportunity_age_slots.front_running;
+        if current_slot.saturating_sub(opportunity.slot) > max_age {
             return Ok(false);
         }
 
@@ -345,6 +347,30 @@ impl ArbitrageStrategy for FrontRunningStrategy {
             return Ok(false);
         }
 
-        Ok(true)
+        // The exit leg follows the pending transaction; once it has landed there is nothing left to front-run
+        let exit_side = match opportunity.route.last() {
+            Some(step) => step.side,
+            None => return Ok(false),
+        };
+        let pending_tx = match self
+            .pending_transactions
+            .iter()
+            .find(|tx| tx.market == opportunity.source_market && tx.side == exit_side)
+        {
+            Some(tx) => tx,
+            None => return Ok(false),
+        };
+        if !self.is_transaction_suitable(pending_tx, market_state)? {
+            return Ok(false);
+        }
+
+        // Re-quote the route against the latest market state
+        let (profit_percentage, _) = self.calculate_front_running_profit(
+            pending_tx,
+            opportunity.required_amount,
+            market_state,
+        )?;
+
+        Ok(profit_percentage >= self.settings.trading.execution.min_profit_threshold)
     }
 }
@@ -81,6 +81,8 @@ impl JitLiquidityStrategy {
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64,
+            slot: market_state.slot,
+            strategy: "jit".to_string(),
         };
 
         Ok(Some(opportunity))
@@ -257,6 +259,11 @@ impl ArbitrageStrategy for JitLiquidityStrategy {
         "JIT Liquidity Strategy"
     }
 
+    fn refresh(&mut self, settings: Arc<Settings>, market_states: Arc<Vec<MarketState>>) {
+        self.settings = settings;
+        self.market_states = market_states;
+    }
+
     fn analyze(&self, markets: &[Pubkey]) -> Result<Vec<ArbitrageOpportunity>, ArbitrageError> {
         self.find_jit_opportunities(markets)
     }
@@ -266,15 +273,20 @@ impl ArbitrageStrategy for JitLiquidityStrategy {
         unimplemented!("JIT execution not implemented")
     }
 
-    fn validate(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError> {
+    fn validate(&self, opportunity: &ArbitrageOpportunity, current_slot: u64) -> Result<bool, ArbitrageError> {
         // Validate opportunity is still viable
-        if SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64
-            - opportunity.timestamp
-            > 5
-        {
+        let max_age = self.settings.trading.execution.max_opportunity_age_slots.jit;
+        if current_slot.saturating_sub(opportunity.slot) > max_age {
+            return Ok(false);
+        }
+
+        // Re-quote the route against the latest market state
+        let market_state = self.get_market_state(&opportunity.source_market)?;
+        if !self.is_market_suitable_for_jit(market_state)? {
+            return Ok(false);
+        }
+        let (profit_percentage, _) = self.calculate_jit_profit(market_state, opportunity.required_amount)?;
+        if profit_percentage < self.settings.trading.execution.min_profit_threshold {
             return Ok(false);
         }
 
@@ -11,6 +11,9 @@ use crate::{
     core::ArbitrageStrategy,
 };
 
+// Strategy types accepted by the factory and `execution_strategies`
+pub const STRATEGY_TYPES: &[&str] = &["jit", "flash_loan", "front_running"];
+
 // Strategy factory for creating different arbitrage strategies
 pub struct StrategyFactory;
 
@@ -33,8 +36,13 @@ pub trait OpportunityValidator {
     fn validate_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError>;
 }
 
-pub trait RiskManager {
+pub trait RiskManager: Send + Sync {
+    // Approving an opportunity reserves its notional until `record_execution` is called; the engine holds
+    // approvals in a `RiskReservation` so they are always released
     fn check_risk_parameters(&self, opportunity: &ArbitrageOpportunity) -> Result<bool, ArbitrageError>;
+
+    // `pnl` is `None` when the trade never reached the network
+    fn record_execution(&self, opportunity: &ArbitrageOpportunity, pnl: Option<i64>) -> Result<(), ArbitrageError>;
 }
 
 pub trait MarketAnalyzer {
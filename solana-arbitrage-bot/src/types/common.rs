@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
-use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitrageOpportunity {
@@ -12,6 +13,8 @@ pub struct ArbitrageOpportunity {
     pub estimated_profit: u64,
     pub route: Vec<TradeStep>,
     pub timestamp: i64,
+    pub slot: u64,
+    pub strategy: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,7 +38,7 @@ pub struct TradeStep {
     pub price: f64,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TradeSide {
     Buy,
     Sell,
@@ -49,6 +52,7 @@ pub struct MarketState {
     pub best_bid: f64,
     pub best_ask: f64,
     pub last_update: i64,
+    pub slot: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,30 +62,34 @@ pub struct FlashLoanParams {
     pub protocol: FlashLoanProtocol,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FlashLoanProtocol {
     Solend,
     Port,
     Marinade,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BotConfig {
-    pub keypair: Option<Keypair>,
-    pub rpc_url: String,
-    pub min_profit_percentage: f64,
-    pub max_trade_size: u64,
-    pub markets_whitelist: Option<Vec<Pubkey>>,
-    pub tokens_whitelist: Option<Vec<Pubkey>>,
-    pub use_flash_loans: bool,
-    pub mev_protection: bool,
-    pub quantum_security: bool,
+impl FromStr for FlashLoanProtocol {
+    type Err = ArbitrageError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source.to_ascii_lowercase().as_str() {
+            "solend" => Ok(FlashLoanProtocol::Solend),
+            "port" => Ok(FlashLoanProtocol::Port),
+            "marinade" => Ok(FlashLoanProtocol::Marinade),
+            _ => Err(ArbitrageError::ConfigError(format!(
+                "Unknown flash loan source: {}",
+                source
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionResult {
     pub success: bool,
-    pub profit_realized: Option<u64>,
+    // Measured from on-chain balance changes; `None` until the trade lands
+    pub profit_realized: Option<i64>,
     pub error: Option<String>,
     pub transaction_signature: Option<String>,
     pub execution_time_ms: u64,
@@ -96,9 +104,13 @@ pub struct MarketPrices {
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum SecurityLevel {
+    #[serde(alias = "low")]
     Low,
+    #[serde(alias = "medium")]
     Medium,
+    #[serde(alias = "high")]
     High,
+    #[serde(alias = "maximum")]
     Maximum,
 }
 
@@ -0,0 +1,173 @@
+use {
+    crate::types::common::ArbitrageError,
+    bip39::{Language, Mnemonic, Seed},
+    solana_sdk::{
+        derivation_path::DerivationPath,
+        signature::{keypair_from_seed, keypair_from_seed_and_derivation_path, Keypair},
+    },
+    std::fmt,
+};
+
+// BIP39 passphrase applied when the key source is a seed phrase
+const SEED_PASSPHRASE_ENV: &str = "KEYPAIR_PASSPHRASE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyFormat {
+    JsonArray,
+    Base58,
+    SeedPhrase,
+}
+
+impl fmt::Display for KeyFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyFormat::JsonArray => write!(f, "JSON byte array"),
+            KeyFormat::Base58 => write!(f, "base58 secret key"),
+            KeyFormat::SeedPhrase => write!(f, "BIP39 seed phrase"),
+        }
+    }
+}
+
+pub fn load_keypair(path: &str, derivation_path: &str) -> Result<Keypair, ArbitrageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        ArbitrageError::ConfigError(format!("Failed to read keypair file {}: {}", path, e))
+    })?;
+
+    let passphrase = std::env::var(SEED_PASSPHRASE_ENV).unwrap_or_default();
+
+    parse_keypair(&contents, derivation_path, &passphrase).map_err(|e| match e {
+        ArbitrageError::ConfigError(reason) => {
+            ArbitrageError::ConfigError(format!("Keypair file {}: {}", path, reason))
+        }
+        other => other,
+    })
+}
+
+pub fn parse_keypair(
+    contents: &str,
+    derivation_path: &str,
+    passphrase: &str,
+) -> Result<Keypair, ArbitrageError> {
+    let contents = contents.trim();
+    let format = detect_format(contents);
+
+    let result = match format {
+        KeyFormat::JsonArray => parse_json_array(contents),
+        KeyFormat::Base58 => parse_base58(contents),
+        KeyFormat::SeedPhrase => parse_seed_phrase(contents, derivation_path, passphrase),
+    };
+
+    result.map_err(|reason| {
+        ArbitrageError::ConfigError(format!("detected {} but {}", format, reason))
+    })
+}
+
+pub fn detect_format(contents: &str) -> KeyFormat {
+    let contents = contents.trim();
+
+    if contents.starts_with('[') || contents.contains(',') {
+        // `solana-keygen` writes a JSON array; older files may omit the brackets
+        KeyFormat::JsonArray
+    } else if contents.split_whitespace().count() > 1 {
+        KeyFormat::SeedPhrase
+    } else {
+        KeyFormat::Base58
+    }
+}
+
+fn parse_json_array(contents: &str) -> Result<Keypair, String> {
+    let bytes: Vec<u8> = if contents.starts_with('[') {
+        serde_json::from_str(contents).map_err(|e| format!("it is not a valid byte array: {}", e))?
+    } else {
+        contents
+            .split(',')
+            .map(|s| s.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("it contains a value that is not a byte: {}", e))?
+    };
+
+    keypair_from_bytes(&bytes)
+}
+
+fn parse_base58(contents: &str) -> Result<Keypair, String> {
+    let bytes = bs58::decode(contents)
+        .into_vec()
+        .map_err(|e| format!("it is not valid base58: {}", e))?;
+
+    keypair_from_bytes(&bytes)
+}
+
+fn parse_seed_phrase(
+    contents: &str,
+    derivation_path: &str,
+    passphrase: &str,
+) -> Result<Keypair, String> {
+    let phrase = contents.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mnemonic = Mnemonic::from_phrase(&phrase, Language::English)
+        .map_err(|e| format!("the phrase is invalid: {}", e))?;
+    let seed = Seed::new(&mnemonic, passphrase);
+
+    // Without a derivation path the seed is used directly, matching `solana-keygen recover`
+    if derivation_path.is_empty() {
+        return keypair_from_seed(seed.as_bytes())
+            .map_err(|e| format!("no keypair could be derived: {}", e));
+    }
+
+    let path = DerivationPath::from_absolute_path_str(derivation_path)
+        .map_err(|e| format!("derivation path `{}` is invalid: {}", derivation_path, e))?;
+
+    keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(path))
+        .map_err(|e| format!("derivation along `{}` failed: {}", derivation_path, e))
+}
+
+fn keypair_from_bytes(bytes: &[u8]) -> Result<Keypair, String> {
+    match bytes.len() {
+        64 => Keypair::from_bytes(bytes).map_err(|e| format!("the key bytes are invalid: {}", e)),
+        // A bare 32-byte secret is the ed25519 seed
+        32 => keypair_from_seed(bytes).map_err(|e| format!("the secret is invalid: {}", e)),
+        len => Err(format!("it holds {} bytes, expected 64 (or a 32-byte secret)", len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::Signer;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_parses_solana_keygen_json() {
+        let keypair = Keypair::new();
+        let json = serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap();
+
+        let loaded = parse_keypair(&format!("{}\n", json), "", "").unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_parses_base58_secret() {
+        let keypair = Keypair::new();
+
+        let loaded = parse_keypair(&keypair.to_base58_string(), "", "").unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_parses_seed_phrase_with_derivation_path() {
+        let direct = parse_keypair(PHRASE, "", "").unwrap();
+        let derived = parse_keypair(PHRASE, "m/44'/501'/0'/0'", "").unwrap();
+        assert_eq!(detect_format(PHRASE), KeyFormat::SeedPhrase);
+        assert_ne!(direct.pubkey(), derived.pubkey());
+    }
+
+    #[test]
+    fn test_errors_name_the_detected_format() {
+        let error = parse_keypair("[1, 2, 3]", "", "").unwrap_err().to_string();
+        assert!(error.contains("JSON byte array"), "{}", error);
+
+        let error = parse_keypair("abandon abandon abandon", "", "").unwrap_err().to_string();
+        assert!(error.contains("BIP39 seed phrase"), "{}", error);
+    }
+}
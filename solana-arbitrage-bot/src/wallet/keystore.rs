@@ -0,0 +1,304 @@
+use {
+    super::load_keypair,
+    crate::{
+        config::{KeystoreSettings, Settings},
+        types::common::ArbitrageError,
+    },
+    aes_gcm::{
+        aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+        Aes256Gcm, Nonce,
+    },
+    argon2::{Algorithm, Argon2, Params, Version},
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    std::{path::Path, str::FromStr},
+    zeroize::Zeroize,
+};
+
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "aes-256-gcm";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+// Argon2id cost parameters; stored in the file so they can be raised without breaking old keystores
+const DEFAULT_MEMORY_KIB: u32 = 64 * 1024;
+const DEFAULT_ITERATIONS: u32 = 3;
+const DEFAULT_PARALLELISM: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreFile {
+    pub version: u32,
+    pub pubkey: String,
+    pub kdf: KdfHeader,
+    pub cipher: CipherHeader,
+    pub ciphertext: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfHeader {
+    pub algorithm: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub salt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherHeader {
+    pub algorithm: String,
+    pub nonce: String,
+}
+
+impl KeystoreFile {
+    pub fn encrypt(keypair: &Keypair, passphrase: &str) -> Result<Self, ArbitrageError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let kdf = KdfHeader {
+            algorithm: KDF_ALGORITHM.to_string(),
+            memory_kib: DEFAULT_MEMORY_KIB,
+            iterations: DEFAULT_ITERATIONS,
+            parallelism: DEFAULT_PARALLELISM,
+            salt: BASE64.encode(salt),
+        };
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let pubkey = keypair.pubkey().to_string();
+
+        let mut key = derive_key(&kdf, passphrase)?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| ArbitrageError::SecurityViolation(format!("Invalid keystore key: {}", e)))?;
+        key.zeroize();
+
+        let mut secret = keypair.to_bytes();
+        let aad = associated_data(KEYSTORE_VERSION, &pubkey);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: &secret, aad: aad.as_bytes() })
+            .map_err(|_| ArbitrageError::SecurityViolation("Keystore encryption failed".to_string()));
+        secret.zeroize();
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey,
+            kdf,
+            cipher: CipherHeader {
+                algorithm: CIPHER_ALGORITHM.to_string(),
+                nonce: BASE64.encode(nonce),
+            },
+            ciphertext: BASE64.encode(ciphertext?),
+        })
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> Result<Keypair, ArbitrageError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(ArbitrageError::ConfigError(format!(
+                "Unsupported keystore version {} (expected {})",
+                self.version, KEYSTORE_VERSION
+            )));
+        }
+        if self.kdf.algorithm != KDF_ALGORITHM || self.cipher.algorithm != CIPHER_ALGORITHM {
+            return Err(ArbitrageError::ConfigError(format!(
+                "Unsupported keystore algorithms {}/{}",
+                self.kdf.algorithm, self.cipher.algorithm
+            )));
+        }
+
+        let nonce = decode_field("cipher.nonce", &self.cipher.nonce)?;
+        let ciphertext = decode_field("ciphertext", &self.ciphertext)?;
+
+        let mut key = derive_key(&self.kdf, passphrase)?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| ArbitrageError::SecurityViolation(format!("Invalid keystore key: {}", e)))?;
+        key.zeroize();
+
+        // The header is authenticated, so a tampered pubkey or version fails like a wrong passphrase
+        let aad = associated_data(self.version, &self.pubkey);
+        let mut secret = cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: aad.as_bytes() })
+            .map_err(|_| {
+                ArbitrageError::SecurityViolation(
+                    "Keystore could not be decrypted: wrong passphrase or corrupted file".to_string(),
+                )
+            })?;
+
+        let keypair = Keypair::from_bytes(&secret)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Keystore holds an invalid keypair: {}", e)));
+        secret.zeroize();
+        let keypair = keypair?;
+
+        let expected = Pubkey::from_str(&self.pubkey)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Keystore pubkey is invalid: {}", e)))?;
+        if keypair.pubkey() != expected {
+            return Err(ArbitrageError::SecurityViolation(
+                "Keystore pubkey does not match the decrypted key".to_string(),
+            ));
+        }
+
+        Ok(keypair)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, ArbitrageError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ArbitrageError::ConfigError(format!("Failed to read keystore {}: {}", path.display(), e))
+        })?;
+
+        serde_json::from_str(&contents).map_err(|e| {
+            ArbitrageError::ConfigError(format!("Keystore {} is malformed: {}", path.display(), e))
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), ArbitrageError> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Failed to serialize keystore: {}", e)))?;
+
+        write_private_file(path, contents.as_bytes())
+    }
+}
+
+pub fn change_passphrase(path: &Path, old_passphrase: &str, new_passphrase: &str) -> Result<(), ArbitrageError> {
+    let keypair = KeystoreFile::read(path)?.decrypt(old_passphrase)?;
+    KeystoreFile::encrypt(&keypair, new_passphrase)?.write(path)
+}
+
+// Unlock the configured keystore using the passphrase environment variable, falling back to a prompt
+pub fn unlock_keystore(settings: &KeystoreSettings) -> Result<Keypair, ArbitrageError> {
+    let keystore = KeystoreFile::read(Path::new(&settings.path))?;
+    let mut passphrase = read_passphrase(&settings.passphrase_env, "Keystore passphrase: ")?;
+    let keypair = keystore.decrypt(&passphrase);
+    passphrase.zeroize();
+    keypair
+}
+
+// Load the master key, preferring the encrypted keystore when enabled
+pub fn load_master_keypair(settings: &Settings) -> Result<Keypair, ArbitrageError> {
+    if settings.security.keystore.enabled {
+        unlock_keystore(&settings.security.keystore)
+    } else {
+        load_keypair(&settings.wallet.keypair_path, &settings.wallet.derivation_path)
+    }
+}
+
+pub fn read_passphrase(env_var: &str, prompt: &str) -> Result<String, ArbitrageError> {
+    if !env_var.is_empty() {
+        if let Ok(passphrase) = std::env::var(env_var) {
+            // Do not leave the secret around for child processes
+            std::env::remove_var(env_var);
+            return Ok(passphrase);
+        }
+    }
+
+    rpassword::prompt_password(prompt)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to read passphrase: {}", e)))
+}
+
+// Written to a 0600 file beside `path`, synced and renamed over it, so a crash leaves either the old
+// contents or the new ones and never a truncated key
+pub fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), ArbitrageError> {
+    use std::io::Write;
+
+    let failed = |e: std::io::Error| ArbitrageError::ConfigError(format!("Failed to write {}: {}", path.display(), e));
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| ArbitrageError::ConfigError(format!("{} is not a file path", path.display())))?;
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+
+    // A leftover from an earlier crash may have looser permissions; start from a fresh file
+    let _ = std::fs::remove_file(&tmp);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let written = options
+        .open(&tmp)
+        .and_then(|mut file| file.write_all(contents).and_then(|_| file.sync_all()))
+        .and_then(|_| std::fs::rename(&tmp, path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(failed(e));
+    }
+
+    // Persist the rename itself
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::File::open(parent).and_then(|dir| dir.sync_all()).map_err(failed)?;
+    }
+
+    Ok(())
+}
+
+fn derive_key(kdf: &KdfHeader, passphrase: &str) -> Result<[u8; KEY_LEN], ArbitrageError> {
+    let salt = decode_field("kdf.salt", &kdf.salt)?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(KEY_LEN))
+        .map_err(|e| ArbitrageError::ConfigError(format!("Invalid keystore KDF parameters: {}", e)))?;
+
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| ArbitrageError::SecurityViolation(format!("Key derivation failed: {}", e)))?;
+
+    Ok(key)
+}
+
+fn associated_data(version: u32, pubkey: &str) -> String {
+    format!("arbitrage-keystore:v{}:{}", version, pubkey)
+}
+
+fn decode_field(name: &str, value: &str) -> Result<Vec<u8>, ArbitrageError> {
+    BASE64
+        .decode(value)
+        .map_err(|e| ArbitrageError::ConfigError(format!("Keystore field {} is not base64: {}", name, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_wrong_passphrase() {
+        let keypair = Keypair::new();
+        let keystore = KeystoreFile::encrypt(&keypair, "correct horse").unwrap();
+
+        assert_eq!(keystore.decrypt("correct horse").unwrap().pubkey(), keypair.pubkey());
+        assert!(keystore.decrypt("battery staple").is_err());
+    }
+
+    #[test]
+    fn test_change_passphrase_replaces_keystore_atomically() {
+        let path = std::env::temp_dir().join(format!("arb-keystore-{}.json", std::process::id()));
+        let keypair = Keypair::new();
+        KeystoreFile::encrypt(&keypair, "old passphrase").unwrap().write(&path).unwrap();
+
+        change_passphrase(&path, "old passphrase", "new passphrase").unwrap();
+        let keystore = KeystoreFile::read(&path).unwrap();
+        assert!(keystore.decrypt("old passphrase").is_err());
+        assert_eq!(keystore.decrypt("new passphrase").unwrap().pubkey(), keypair.pubkey());
+        assert!(!path.with_file_name(format!("arb-keystore-{}.json.tmp", std::process::id())).exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_tampered_header_is_rejected() {
+        let keypair = Keypair::new();
+        let mut keystore = KeystoreFile::encrypt(&keypair, "passphrase").unwrap();
+        keystore.pubkey = Keypair::new().pubkey().to_string();
+
+        assert!(keystore.decrypt("passphrase").is_err());
+    }
+}
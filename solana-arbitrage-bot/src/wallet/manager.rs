@@ -0,0 +1,256 @@
+use {
+    super::{ExecutionPool, WalletLease},
+    crate::{
+        config::SettingsHandle,
+        journal::{Journal, RotationRecord, TransferKind, TransferRecord},
+        types::common::ArbitrageError,
+    },
+    solana_sdk::{
+        derivation_path::DerivationPath,
+        pubkey::Pubkey,
+        signature::{keypair_from_seed_and_derivation_path, Keypair, Signer},
+        system_instruction,
+    },
+    std::{
+        str::FromStr,
+        sync::{Arc, RwLock},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::watch,
+};
+
+// Lamports reserved for the fee of the sweep transfer itself
+const TRANSFER_FEE_LAMPORTS: u64 = 5000;
+// How soon a rotation postponed by a busy wallet is tried again
+const ROTATION_RETRY: Duration = Duration::from_secs(10);
+
+pub struct WalletManager {
+    settings: SettingsHandle,
+    journal: Arc<Journal>,
+    wallets: Vec<Arc<Keypair>>,
+    active: RwLock<usize>,
+}
+
+impl WalletManager {
+    pub fn new(settings: SettingsHandle, journal: Arc<Journal>, master: &Keypair) -> Result<Self, ArbitrageError> {
+        let pool_size = settings.load().wallet.pool_size.max(1);
+
+        // Trading wallets are derived from the master secret so the pool is reproducible after a restart
+        let wallets = (0..pool_size)
+            .map(|index| derive_wallet(master, index).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            settings,
+            journal,
+            wallets,
+            active: RwLock::new(0),
+        })
+    }
+
+    pub fn active(&self) -> Arc<Keypair> {
+        self.wallets[*self.active.read().unwrap()].clone()
+    }
+
+    pub fn wallets(&self) -> &[Arc<Keypair>] {
+        &self.wallets
+    }
+
+    // Switches the active wallet every interval; returns on shutdown, after a rotation already running
+    pub async fn run(self: Arc<Self>, pool: Arc<ExecutionPool>, mut shutdown: watch::Receiver<bool>) {
+        let interval_secs = self.settings.load().wallet.key_rotation_interval;
+        if interval_secs == 0 || self.wallets.len() < 2 {
+            log::info!("Hot wallet rotation disabled");
+            return;
+        }
+        pool.set_active(self.active().pubkey());
+
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        // The first tick completes immediately; rotate only after a full interval
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => {}
+            }
+
+            // Trades keep the active wallet busy most of the time, so a postponed rotation is retried soon
+            while !*shutdown.borrow() {
+                let manager = self.clone();
+                let rotation_pool = pool.clone();
+                match tokio::task::spawn_blocking(move || manager.rotate(&rotation_pool)).await {
+                    Ok(Ok(true)) => break,
+                    Ok(Ok(false)) => log::debug!("Hot wallet rotation postponed, a wallet is busy"),
+                    Ok(Err(e)) => {
+                        log::error!("Hot wallet rotation failed: {}", e);
+                        break;
+                    }
+                    Err(e) => {
+                        log::error!("Hot wallet rotation task failed: {}", e);
+                        break;
+                    }
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(ROTATION_RETRY) => {}
+                    _ = shutdown.changed() => {}
+                }
+            }
+            if *shutdown.borrow() {
+                return;
+            }
+        }
+    }
+
+    // Returns false without moving anything while the active or the incoming wallet is leased
+    pub fn rotate(&self, pool: &Arc<ExecutionPool>) -> Result<bool, ArbitrageError> {
+        let settings = self.settings.load();
+        let (current_index, next_index) = self.rotation();
+        let current = self.wallets[current_index].clone();
+        let next = self.wallets[next_index].clone();
+
+        // Hold both wallets so no trade starts on either while fee SOL moves and the retired one is swept
+        let (current_lease, _next_lease) = match (
+            pool.lease_wallet(&current.pubkey()),
+            pool.lease_wallet(&next.pubkey()),
+        ) {
+            (Some(current_lease), Some(next_lease)) => (current_lease, next_lease),
+            _ => return Ok(false),
+        };
+
+        // Make sure the incoming wallet can pay fees before it starts signing
+        let next_balance = pool.get_balance(&next.pubkey())?;
+        if let Some(lamports) = top_up_amount(next_balance, settings.wallet.fee_reserve_lamports) {
+            self.transfer(pool, &current_lease, &next.pubkey(), lamports, TransferKind::TopUp)?;
+        }
+
+        self.activate(next_index);
+        pool.set_active(next.pubkey());
+        log::info!("Rotated active hot wallet {} -> {}", current.pubkey(), next.pubkey());
+
+        // The switch has happened, so a journal failure is only logged
+        if let Err(e) = self.journal.record_rotation(&RotationRecord {
+            timestamp: now(),
+            retired: current.pubkey().to_string(),
+            active: next.pubkey().to_string(),
+        }) {
+            log::error!("{}", e);
+        }
+
+        self.sweep(pool, &current_lease)?;
+        Ok(true)
+    }
+
+    // The active wallet and the one that takes over from it
+    fn rotation(&self) -> (usize, usize) {
+        let current = *self.active.read().unwrap();
+        (current, (current + 1) % self.wallets.len())
+    }
+
+    fn activate(&self, index: usize) {
+        *self.active.write().unwrap() = index;
+    }
+
+    fn sweep(&self, pool: &ExecutionPool, lease: &WalletLease) -> Result<(), ArbitrageError> {
+        let settings = self.settings.load();
+        if settings.wallet.cold_address.is_empty() {
+            return Ok(());
+        }
+
+        let cold_address = Pubkey::from_str(&settings.wallet.cold_address)
+            .map_err(|e| ArbitrageError::ConfigError(format!("Invalid wallet.cold_address: {}", e)))?;
+
+        let balance = pool.get_balance(&lease.pubkey())?;
+        let keep = settings.wallet.sweep_floor_lamports + TRANSFER_FEE_LAMPORTS;
+        if balance <= keep {
+            return Ok(());
+        }
+
+        self.transfer(pool, lease, &cold_address, balance - keep, TransferKind::Sweep)
+    }
+
+    fn transfer(
+        &self,
+        pool: &ExecutionPool,
+        from: &WalletLease,
+        to: &Pubkey,
+        lamports: u64,
+        kind: TransferKind,
+    ) -> Result<(), ArbitrageError> {
+        let instruction = system_instruction::transfer(&from.pubkey(), to, lamports);
+        let signature = pool.send_instructions(&from.signer(), &[instruction])?;
+
+        log::info!("{:?} of {} lamports {} -> {}: {}", kind, lamports, from.pubkey(), to, signature);
+
+        self.journal.record_transfer(&TransferRecord {
+            timestamp: now(),
+            kind,
+            from: from.pubkey().to_string(),
+            to: to.to_string(),
+            mint: None,
+            amount: lamports,
+            signature: signature.to_string(),
+        })
+    }
+}
+
+// Lamports that bring the incoming wallet up to the fee reserve, if it is short
+fn top_up_amount(balance: u64, fee_reserve: u64) -> Option<u64> {
+    Some(fee_reserve.saturating_sub(balance)).filter(|lamports| *lamports > 0)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+pub fn derive_wallet(master: &Keypair, index: u32) -> Result<Keypair, ArbitrageError> {
+    let path = DerivationPath::new_bip44(Some(index), Some(0));
+
+    keypair_from_seed_and_derivation_path(master.secret().as_bytes(), Some(path))
+        .map_err(|e| ArbitrageError::ConfigError(format!("Failed to derive wallet {}: {}", index, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::config::Settings,
+        std::path::Path,
+    };
+
+    fn manager(pool_size: u32) -> WalletManager {
+        let mut settings = Settings::default();
+        settings.wallet.pool_size = pool_size;
+        let journal = Arc::new(Journal::open(Path::new(":memory:")).unwrap());
+        WalletManager::new(SettingsHandle::new(settings), journal, &Keypair::new()).unwrap()
+    }
+
+    #[test]
+    fn test_rotation_cycles_through_derived_wallets_in_order() {
+        let manager = manager(3);
+        assert_eq!(manager.active().pubkey(), manager.wallets()[0].pubkey());
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            let (current, next) = manager.rotation();
+            order.push((current, next));
+            manager.activate(next);
+        }
+
+        assert_eq!(order, vec![(0, 1), (1, 2), (2, 0), (0, 1)]);
+        assert_eq!(manager.active().pubkey(), manager.wallets()[1].pubkey());
+    }
+
+    #[test]
+    fn test_top_up_fills_incoming_wallet_to_fee_reserve() {
+        assert_eq!(top_up_amount(10_000_000, 50_000_000), Some(40_000_000));
+        assert_eq!(top_up_amount(0, 50_000_000), Some(50_000_000));
+        // Already holds enough for fees
+        assert_eq!(top_up_amount(50_000_000, 50_000_000), None);
+        assert_eq!(top_up_amount(80_000_000, 50_000_000), None);
+    }
+}
@@ -0,0 +1,11 @@
+mod keys;
+mod keystore;
+mod manager;
+mod pool;
+mod token;
+
+pub use keys::*;
+pub use keystore::*;
+pub use manager::*;
+pub use pool::*;
+pub use token::*;
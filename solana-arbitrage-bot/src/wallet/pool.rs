@@ -0,0 +1,532 @@
+use {
+    super::{associated_token_address, create_associated_token_account_idempotent, token_transfer},
+    crate::{
+        config::SettingsHandle,
+        journal::{Journal, TransferKind, TransferRecord},
+        signer::SharedSigner,
+        types::common::{ArbitrageError, ExecutionResult},
+    },
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Signature, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        fmt,
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::watch,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct WalletStats {
+    pub trades: u64,
+    pub failed: u64,
+    pub realized_pnl: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletReport {
+    pub pubkey: Pubkey,
+    pub lamports: u64,
+    pub tokens: Vec<(Pubkey, u64)>,
+    pub in_flight: bool,
+    pub stats: WalletStats,
+}
+
+impl fmt::Display for WalletReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}: {:.4} SOL, {} trades ({} failed), PnL {:.4} SOL",
+            self.pubkey,
+            if self.in_flight { " [busy]" } else { "" },
+            self.lamports as f64 / 1e9,
+            self.stats.trades,
+            self.stats.failed,
+            self.stats.realized_pnl as f64 / 1e9
+        )?;
+        for (mint, amount) in &self.tokens {
+            write!(f, ", {} {}", amount, mint)?;
+        }
+        Ok(())
+    }
+}
+
+// Execution wallets handed out one per in-flight trade so concurrent trades never
+// share a fee payer or token account
+pub struct ExecutionPool {
+    settings: SettingsHandle,
+    rpc_client: Arc<RpcClient>,
+    journal: Arc<Journal>,
+    wallets: Vec<SharedSigner>,
+    // Wallets leased to a trade or a maintenance transfer
+    in_flight: Mutex<HashSet<Pubkey>>,
+    // The rotated hot wallet, handed out first whenever it is idle
+    active: Mutex<Option<Pubkey>>,
+    stats: Mutex<HashMap<Pubkey, WalletStats>>,
+    last_lease: Mutex<Instant>,
+}
+
+// Returns the wallet to the pool when dropped
+pub struct WalletLease {
+    pool: Arc<ExecutionPool>,
+    signer: SharedSigner,
+}
+
+impl WalletLease {
+    pub fn signer(&self) -> SharedSigner {
+        self.signer.clone()
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+}
+
+impl Drop for WalletLease {
+    fn drop(&mut self) {
+        self.pool.in_flight.lock().unwrap().remove(&self.signer.pubkey());
+    }
+}
+
+impl ExecutionPool {
+    pub fn new(
+        settings: SettingsHandle,
+        rpc_client: Arc<RpcClient>,
+        journal: Arc<Journal>,
+        wallets: Vec<SharedSigner>,
+    ) -> Self {
+        Self {
+            settings,
+            rpc_client,
+            journal,
+            wallets,
+            in_flight: Mutex::new(HashSet::new()),
+            active: Mutex::new(None),
+            stats: Mutex::new(HashMap::new()),
+            last_lease: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn wallets(&self) -> &[SharedSigner] {
+        &self.wallets
+    }
+
+    pub fn set_active(&self, wallet: Pubkey) {
+        *self.active.lock().unwrap() = Some(wallet);
+    }
+
+    // Token accounts are associated with their wallet, so leasing the wallet also keeps its accounts to one trade
+    pub fn lease(self: &Arc<Self>) -> Option<WalletLease> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+
+        // Prefer the active wallet, then the idle wallet with the fewest trades so load spreads evenly
+        let active = *self.active.lock().unwrap();
+        let stats = self.stats.lock().unwrap();
+        let signer = self
+            .wallets
+            .iter()
+            .filter(|signer| !in_flight.contains(&signer.pubkey()))
+            .min_by_key(|signer| {
+                let pubkey = signer.pubkey();
+                (Some(pubkey) != active, stats.get(&pubkey).map(|s| s.trades).unwrap_or(0))
+            })?
+            .clone();
+        drop(stats);
+
+        in_flight.insert(signer.pubkey());
+        *self.last_lease.lock().unwrap() = Instant::now();
+
+        Some(WalletLease {
+            pool: self.clone(),
+            signer,
+        })
+    }
+
+    // Reserve one particular wallet for a maintenance transfer; `None` while a trade or other task holds it
+    pub fn lease_wallet(self: &Arc<Self>, wallet: &Pubkey) -> Option<WalletLease> {
+        let signer = self.wallets.iter().find(|signer| signer.pubkey() == *wallet)?.clone();
+
+        if !self.in_flight.lock().unwrap().insert(*wallet) {
+            return None;
+        }
+
+        Some(WalletLease {
+            pool: self.clone(),
+            signer,
+        })
+    }
+
+    pub fn record_result(&self, wallet: &Pubkey, result: &ExecutionResult) {
+        let mut stats = self.stats.lock().unwrap();
+        let entry = stats.entry(*wallet).or_default();
+
+        entry.trades += 1;
+        if result.success {
+            entry.realized_pnl += result.profit_realized.unwrap_or(0);
+        } else {
+            entry.failed += 1;
+        }
+    }
+
+    pub fn report(&self) -> Result<Vec<WalletReport>, ArbitrageError> {
+        let mints = self.tracked_mints();
+        let in_flight = self.in_flight.lock().unwrap().clone();
+        let stats = self.stats.lock().unwrap().clone();
+
+        self.wallets
+            .iter()
+            .map(|signer| {
+                let pubkey = signer.pubkey();
+                let tokens = mints
+                    .iter()
+                    .map(|mint| Ok((*mint, self.token_balance(&pubkey, mint)?)))
+                    .collect::<Result<Vec<_>, ArbitrageError>>()?;
+
+                Ok(WalletReport {
+                    pubkey,
+                    lamports: self.get_balance(&pubkey)?,
+                    tokens,
+                    in_flight: in_flight.contains(&pubkey),
+                    stats: stats.get(&pubkey).cloned().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    // Returns on shutdown; a rebalance already running finishes first
+    pub async fn run(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) {
+        let interval_secs = self.settings.load().wallet.rebalance_interval;
+        if interval_secs == 0 {
+            log::info!("Execution wallet rebalancing disabled");
+            return;
+        }
+
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => {}
+            }
+            if *shutdown.borrow() {
+                return;
+            }
+
+            if self.wallets.len() > 1 {
+                let pool = self.clone();
+                if let Err(e) = self.blocking(move |_| pool.rebalance()).await {
+                    log::error!("Execution wallet rebalance failed: {}", e);
+                }
+            }
+
+            match self.blocking(|pool| pool.report()).await {
+                Ok(reports) => {
+                    for report in reports {
+                        log::info!("Wallet {}", report);
+                    }
+                }
+                Err(e) => log::warn!("Failed to report wallet balances: {}", e),
+            }
+        }
+    }
+
+    pub fn rebalance(self: &Arc<Self>) -> Result<(), ArbitrageError> {
+        let settings = self.settings.load();
+
+        // Plan only across idle wallets; `submit` leases both ends before anything moves
+        let idle: Vec<SharedSigner> = {
+            let in_flight = self.in_flight.lock().unwrap();
+            self.wallets
+                .iter()
+                .filter(|signer| !in_flight.contains(&signer.pubkey()))
+                .cloned()
+                .collect()
+        };
+        if idle.len() < 2 {
+            return Ok(());
+        }
+
+        // Keep every wallet able to pay fees
+        let fee_reserve = settings.wallet.fee_reserve_lamports;
+        let balances = idle
+            .iter()
+            .map(|signer| self.get_balance(&signer.pubkey()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some((from, to, amount)) = plan_transfer(&balances, fee_reserve, settings.wallet.rebalance_threshold) {
+            let to_pubkey = idle[to].pubkey();
+            let instruction = system_instruction::transfer(&idle[from].pubkey(), &to_pubkey, amount);
+            self.submit(&idle[from], &to_pubkey, None, amount, vec![instruction])?;
+        }
+
+        // Spread each traded token evenly across the pool
+        for mint in self.tracked_mints() {
+            let balances = idle
+                .iter()
+                .map(|signer| self.token_balance(&signer.pubkey(), &mint))
+                .collect::<Result<Vec<_>, _>>()?;
+            let target = balances.iter().sum::<u64>() / balances.len() as u64;
+
+            if let Some((from, to, amount)) = plan_transfer(&balances, target, settings.wallet.rebalance_threshold) {
+                let owner = idle[from].pubkey();
+                let recipient = idle[to].pubkey();
+                let instructions = vec![
+                    create_associated_token_account_idempotent(&owner, &recipient, &mint),
+                    token_transfer(
+                        &associated_token_address(&owner, &mint),
+                        &associated_token_address(&recipient, &mint),
+                        &owner,
+                        amount,
+                    ),
+                ];
+                self.submit(&idle[from], &recipient, Some(mint), amount, instructions)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The RPC client blocks, so async callers run pool RPC work on the blocking pool instead of a runtime worker
+    pub async fn blocking<T, F>(self: &Arc<Self>, call: F) -> Result<T, ArbitrageError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&ExecutionPool) -> Result<T, ArbitrageError> + Send + 'static,
+    {
+        let pool = self.clone();
+        tokio::task::spawn_blocking(move || call(&pool))
+            .await
+            .map_err(|e| ArbitrageError::NetworkError(format!("Blocking RPC task failed: {}", e)))?
+    }
+
+    // How long no trade has been running or started
+    pub fn idle_for(&self) -> Duration {
+        if !self.in_flight.lock().unwrap().is_empty() {
+            return Duration::ZERO;
+        }
+        self.last_lease.lock().unwrap().elapsed()
+    }
+
+    pub fn is_in_flight(&self, wallet: &Pubkey) -> bool {
+        self.in_flight.lock().unwrap().contains(wallet)
+    }
+
+    pub fn send_instructions(
+        &self,
+        from: &SharedSigner,
+        instructions: &[Instruction],
+    ) -> Result<Signature, ArbitrageError> {
+        let blockhash = self
+            .rpc_client
+            .get_latest_blockhash()
+            .map_err(|e| ArbitrageError::NetworkError(format!("Failed to fetch blockhash: {}", e)))?;
+
+        let signers: [Arc<dyn Signer>; 1] = [from.clone()];
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&from.pubkey()));
+        transaction
+            .try_sign(&signers, blockhash)
+            .map_err(|e| ArbitrageError::SecurityViolation(format!("Signing failed: {}", e)))?;
+
+        self.rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(|e| ArbitrageError::TransactionError(format!("Transaction from {} failed: {}", from.pubkey(), e)))
+    }
+
+    fn submit(
+        self: &Arc<Self>,
+        from: &SharedSigner,
+        to: &Pubkey,
+        mint: Option<Pubkey>,
+        amount: u64,
+        instructions: Vec<Instruction>,
+    ) -> Result<(), ArbitrageError> {
+        // Hold both wallets until the transfer lands so no trade starts on either mid-transfer
+        let (_from_lease, _to_lease) = match (self.lease_wallet(&from.pubkey()), self.lease_wallet(to)) {
+            (Some(from_lease), Some(to_lease)) => (from_lease, to_lease),
+            _ => {
+                log::debug!("Skipping rebalance {} -> {}: a wallet was leased since planning", from.pubkey(), to);
+                return Ok(());
+            }
+        };
+
+        let signature = self.send_instructions(from, &instructions)?;
+
+        log::info!(
+            "Rebalanced {} {} {} -> {}: {}",
+            amount,
+            mint.map(|mint| mint.to_string()).unwrap_or_else(|| "lamports".to_string()),
+            from.pubkey(),
+            to,
+            signature
+        );
+
+        self.journal.record_transfer(&TransferRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64,
+            kind: TransferKind::Rebalance,
+            from: from.pubkey().to_string(),
+            to: to.to_string(),
+            mint: mint.map(|mint| mint.to_string()),
+            amount,
+            signature: signature.to_string(),
+        })
+    }
+
+    fn tracked_mints(&self) -> Vec<Pubkey> {
+        self.settings
+            .load()
+            .trading
+            .markets
+            .whitelisted_tokens
+            .iter()
+            .filter_map(|mint| Pubkey::from_str(mint).ok())
+            .collect()
+    }
+
+    pub fn get_balance(&self, pubkey: &Pubkey) -> Result<u64, ArbitrageError> {
+        self.rpc_client
+            .get_balance(pubkey)
+            .map_err(|e| ArbitrageError::NetworkError(format!("Failed to fetch balance of {}: {}", pubkey, e)))
+    }
+
+    pub fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> Result<u64, ArbitrageError> {
+        let account = associated_token_address(owner, mint);
+
+        // A missing token account simply holds nothing yet
+        if self
+            .rpc_client
+            .get_account_with_commitment(&account, self.rpc_client.commitment())
+            .map_err(|e| ArbitrageError::NetworkError(format!("Failed to fetch {}: {}", account, e)))?
+            .value
+            .is_none()
+        {
+            return Ok(0);
+        }
+
+        let balance = self
+            .rpc_client
+            .get_token_account_balance(&account)
+            .map_err(|e| ArbitrageError::NetworkError(format!("Failed to fetch token balance of {}: {}", account, e)))?;
+        balance
+            .amount
+            .parse()
+            .map_err(|e| ArbitrageError::NetworkError(format!("Invalid token balance for {}: {}", account, e)))
+    }
+}
+
+// Pick one transfer from the richest wallet to the poorest when the poorest has fallen
+// more than `threshold` below `target`; the donor never drops below the target itself
+fn plan_transfer(balances: &[u64], target: u64, threshold: f64) -> Option<(usize, usize, u64)> {
+    let (to, poorest) = balances.iter().copied().enumerate().min_by_key(|(_, balance)| *balance)?;
+    let (from, richest) = balances.iter().copied().enumerate().max_by_key(|(_, balance)| *balance)?;
+
+    if from == to || (poorest as f64) >= target as f64 * (1.0 - threshold) {
+        return None;
+    }
+
+    let amount = (target - poorest).min(richest.saturating_sub(target));
+    if amount == 0 {
+        return None;
+    }
+
+    Some((from, to, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::config::Settings,
+        solana_sdk::signature::Keypair,
+        std::path::Path,
+    };
+
+    fn pool(wallets: usize) -> Arc<ExecutionPool> {
+        let rpc_client = Arc::new(RpcClient::new("http://127.0.0.1:1".to_string()));
+        let journal = Arc::new(Journal::open(Path::new(":memory:")).unwrap());
+        let signers: Vec<SharedSigner> = (0..wallets).map(|_| Arc::new(Keypair::new()) as SharedSigner).collect();
+        Arc::new(ExecutionPool::new(SettingsHandle::new(Settings::default()), rpc_client, journal, signers))
+    }
+
+    fn trade(success: bool) -> ExecutionResult {
+        ExecutionResult {
+            success,
+            profit_realized: None,
+            error: None,
+            transaction_signature: None,
+            execution_time_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_lease_never_hands_out_an_in_flight_wallet() {
+        let pool = pool(2);
+
+        let first = pool.lease().unwrap();
+        let second = pool.lease().unwrap();
+        assert_ne!(first.pubkey(), second.pubkey());
+        assert!(pool.is_in_flight(&first.pubkey()) && pool.is_in_flight(&second.pubkey()));
+        assert!(pool.lease().is_none());
+    }
+
+    #[test]
+    fn test_dropping_a_lease_releases_the_wallet() {
+        let pool = pool(1);
+
+        let lease = pool.lease().unwrap();
+        let wallet = lease.pubkey();
+        assert!(pool.lease().is_none());
+
+        drop(lease);
+        assert!(!pool.is_in_flight(&wallet));
+        assert_eq!(pool.lease().unwrap().pubkey(), wallet);
+    }
+
+    #[test]
+    fn test_lease_wallet_fails_while_the_wallet_is_leased() {
+        let pool = pool(2);
+        let wallet = pool.wallets()[0].pubkey();
+
+        let lease = pool.lease_wallet(&wallet).unwrap();
+        assert!(pool.lease_wallet(&wallet).is_none());
+        // The other wallet is still free for trades
+        assert_ne!(pool.lease().unwrap().pubkey(), wallet);
+
+        drop(lease);
+        assert!(pool.lease_wallet(&wallet).is_some());
+        assert!(pool.lease_wallet(&Pubkey::new_unique()).is_none());
+    }
+
+    #[test]
+    fn test_lease_prefers_the_active_wallet_then_the_least_used() {
+        let pool = pool(3);
+        let wallets: Vec<Pubkey> = pool.wallets().iter().map(|signer| signer.pubkey()).collect();
+        pool.record_result(&wallets[0], &trade(true));
+        pool.record_result(&wallets[1], &trade(false));
+        pool.record_result(&wallets[1], &trade(true));
+
+        // The active wallet wins even though it has traded the most
+        pool.set_active(wallets[1]);
+        let active = pool.lease().unwrap();
+        assert_eq!(active.pubkey(), wallets[1]);
+
+        // Otherwise the idle wallet with the fewest trades
+        assert_eq!(pool.lease().unwrap().pubkey(), wallets[2]);
+    }
+
+    #[test]
+    fn test_plan_transfer_moves_from_richest_to_poorest() {
+        assert_eq!(plan_transfer(&[100, 10, 70], 60, 0.25), Some((0, 1, 40)));
+        // Within the threshold
+        assert_eq!(plan_transfer(&[70, 50, 60], 60, 0.25), None);
+        // Nobody has spare funds
+        assert_eq!(plan_transfer(&[10, 20], 60, 0.25), None);
+    }
+}